@@ -0,0 +1,63 @@
+//! Compares trying every rule against the dispatcher on the corpus logs,
+//! run with `cargo bench --bench dispatch`.
+
+use std::{fs, hint::black_box, path::Path};
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use fg_telemetry_client::parser::{
+    ParseResult,
+    dispatch::RULE_DISPATCHER,
+    rules::{FGParserRule, rules},
+};
+
+fn corpus_lines() -> Vec<String> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/corpus");
+    let mut paths: Vec<_> = fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "log"))
+        .collect();
+    paths.sort();
+    paths
+        .iter()
+        .flat_map(|path| {
+            let log = fs::read_to_string(path).unwrap();
+            log.lines().map(str::to_owned).collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Returns the number of the lines parsed by the first rule handling them.
+fn count_parsed<'a, I>(lines: &[String], candidates: impl Fn(&str) -> I) -> usize
+where
+    I: Iterator<Item = &'a FGParserRule>,
+{
+    lines
+        .iter()
+        .filter(|line| {
+            candidates(line).any(|rule| matches!((rule.parse)(line), ParseResult::Parsed(_)))
+        })
+        .count()
+}
+
+fn bench_dispatch(c: &mut Criterion) {
+    let lines = corpus_lines();
+    let every_rule = rules();
+    let dispatcher = &*RULE_DISPATCHER;
+    assert_eq!(
+        count_parsed(&lines, |_| every_rule.iter()),
+        count_parsed(&lines, |line| dispatcher.candidates(line))
+    );
+
+    let mut group = c.benchmark_group("dispatch");
+    group.bench_function("every_rule", |b| {
+        b.iter(|| count_parsed(black_box(&lines), |_| every_rule.iter()))
+    });
+    group.bench_function("aho_corasick", |b| {
+        b.iter(|| count_parsed(black_box(&lines), |line| dispatcher.candidates(line)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_dispatch);
+criterion_main!(benches);
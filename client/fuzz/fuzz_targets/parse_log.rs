@@ -0,0 +1,20 @@
+//! Feeds the text line by line through the parser, like `task_parser` does for the log file.
+#![no_main]
+
+use fg_telemetry_client::{
+    parser::log_parser::{FGLogParser, MAX_BUFFER_LINES},
+    test_support::test_clock,
+};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    // Old logs are not always valid UTF-8, the readers replace the invalid bytes as well.
+    let text = String::from_utf8_lossy(data);
+    let mut parser = FGLogParser::new(test_clock());
+
+    for line in text.lines() {
+        // The errors are fine, the rules only must not panic.
+        let _ = parser.parse_line(line);
+        assert!(parser.buffered_lines() <= MAX_BUFFER_LINES);
+    }
+});
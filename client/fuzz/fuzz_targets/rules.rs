@@ -0,0 +1,18 @@
+//! Calls every rule with the whole text, including the multi-line input they never requested.
+#![no_main]
+
+use std::sync::LazyLock;
+
+use fg_telemetry_client::parser::rules::{FGParserRule, rules};
+use libfuzzer_sys::fuzz_target;
+
+static RULES: LazyLock<Vec<FGParserRule>> = LazyLock::new(rules);
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(text) = std::str::from_utf8(data) else {
+        return;
+    };
+    for rule in RULES.iter() {
+        let _ = (rule.parse)(text);
+    }
+});
@@ -0,0 +1,239 @@
+use temporal_rs::Instant;
+
+use super::{qualification::FGQualificationTracker, roster::FGRosterTracker};
+use crate::{
+    lifecycle::episode::FGEpisodeState,
+    models::{
+        exports::{
+            FGExportsCurrent, FGExportsLobbyInfo, FGExportsMatchmaking, FGExportsRoundInfo,
+            FGExportsRoundResult,
+        },
+        messages::FGGameMessage,
+        state::FGGameState,
+    },
+};
+
+/// Reduces the parsed messages into a single `FGExportsCurrent`.
+#[derive(Debug, Clone, Default)]
+pub struct FGCurrentAggregator {
+    current: FGExportsCurrent,
+    episode: FGEpisodeState,
+    qualification: FGQualificationTracker,
+    roster: FGRosterTracker,
+}
+
+impl FGCurrentAggregator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn current(&self) -> &FGExportsCurrent {
+        &self.current
+    }
+
+    /// Applies the message, returns true if the current snapshot has been changed.
+    pub fn apply(&mut self, message: &FGGameMessage, time: Option<&Instant>) -> bool {
+        let before = self.current.clone();
+        let epoch_millis = time.map(|time| time.epoch_milliseconds());
+
+        if self.current.qualification.is_some() && self.qualification.apply(message) {
+            self.current.qualification = Some(self.qualification.qualification());
+        }
+        if self.current.roster.is_some() && self.roster.apply(message) {
+            self.current.roster = Some(self.roster.roster());
+        }
+        let round_changed = self.episode.apply(message);
+        match message {
+            FGGameMessage::GameStateChanged { after, .. } => {
+                if *after == FGGameState::StateMainMenu {
+                    self.reset_episode();
+                    self.current.gamemode = None;
+                }
+                self.current.state = Some(after.clone());
+            }
+            FGGameMessage::BeginMatchmaking => {
+                self.reset_episode();
+                self.current.gamemode = None;
+            }
+            FGGameMessage::LogRotated(_) => {
+                self.reset_episode();
+                self.current.gamemode = None;
+                self.current.state = None;
+            }
+            FGGameMessage::MatchmakingMessageReceived(matchmaking) => {
+                self.current.matchmaking = FGExportsMatchmaking(Some(matchmaking.clone()));
+            }
+            FGGameMessage::SuccessfullyJoined { game_mode, .. } => {
+                self.reset_episode();
+                self.current.gamemode = Some(game_mode.clone());
+            }
+            FGGameMessage::GameSessionState { after, .. } => {
+                self.current.game_session_state = Some(after.clone());
+            }
+            FGGameMessage::LoadedRound(round) => {
+                self.current.round_info = Some(FGExportsRoundInfo {
+                    round_order: self.episode.round().map_or(0, |round| round.order),
+                    round_id_str: round.id.clone(),
+                    round_display_name: round.display_name.clone(),
+                    duration: round
+                        .profile
+                        .as_ref()
+                        .and_then(|profile| profile.time_limit),
+                    profile: round.profile.clone(),
+                });
+                let team_size = self
+                    .current
+                    .gamemode
+                    .as_ref()
+                    .map_or(1, |mode| mode.team_size());
+                self.qualification =
+                    FGQualificationTracker::start_round(round.profile.as_ref(), team_size);
+                self.current.qualification = Some(self.qualification.qualification());
+                self.roster = FGRosterTracker::new();
+                self.current.roster = Some(self.roster.roster());
+                self.current.game_session_state = None;
+                self.current.round_result = None;
+                self.lobby_info().platforms.clear();
+            }
+            FGGameMessage::SetPlayerProgress { .. } if round_changed => {
+                if let Some(qualified) = self.episode.round().and_then(|round| round.qualified) {
+                    self.round_result().qualified = qualified;
+                }
+            }
+            FGGameMessage::RoundOver => {
+                let round_over_time = epoch_millis.and_then(|millis| usize::try_from(millis).ok());
+                self.round_result().round_over_time = round_over_time;
+            }
+            FGGameMessage::GameLobbyRewards(dto) => {
+                let round_order = self
+                    .current
+                    .round_info
+                    .as_ref()
+                    .map(|info| info.round_order);
+                let round = dto
+                    .rounds
+                    .iter()
+                    .find(|round| Some(round.round_order) == round_order)
+                    .or_else(|| dto.rounds.last());
+                if let Some(round) = round {
+                    let (qualified, badge) = (round.qualified, round.badge_id.clone());
+                    let round_result = self.round_result();
+                    round_result.qualified = qualified;
+                    round_result.badge = Some(badge);
+                }
+            }
+            FGGameMessage::AppendSpectatorTarget { .. } if round_changed => {
+                if let Some(platforms) = self.episode.round().map(|round| round.platforms.clone()) {
+                    self.lobby_info().platforms = platforms;
+                }
+            }
+            FGGameMessage::NetworkMetrics { latency } => {
+                let lobby_info = self.lobby_info();
+                lobby_info.latency = Some(*latency);
+                lobby_info.latency_from_time =
+                    epoch_millis.and_then(|millis| millis.try_into().ok());
+            }
+            _ => {}
+        }
+
+        self.current != before
+    }
+
+    /// Clears everything belongs to the previous episode.
+    fn reset_episode(&mut self) {
+        self.current.matchmaking = FGExportsMatchmaking(None);
+        self.current.game_session_state = None;
+        self.current.round_info = None;
+        self.current.round_result = None;
+        self.current.lobby_info = None;
+        self.current.qualification = None;
+        self.qualification = FGQualificationTracker::default();
+        self.current.roster = None;
+        self.roster = FGRosterTracker::new();
+        self.episode.reset();
+    }
+
+    fn round_result(&mut self) -> &mut FGExportsRoundResult {
+        self.current
+            .round_result
+            .get_or_insert_with(Default::default)
+    }
+
+    fn lobby_info(&mut self) -> &mut FGExportsLobbyInfo {
+        self.current.lobby_info.get_or_insert_with(Default::default)
+    }
+}
+
+#[test]
+fn aggregate_round_result() {
+    use crate::models::common::{FGGameMode, FGRoundInfo};
+
+    let mut aggregator = FGCurrentAggregator::new();
+    aggregator.apply(
+        &FGGameMessage::SuccessfullyJoined {
+            game_mode: FGGameMode::ClassicSolo,
+            session_text: None,
+        },
+        None,
+    );
+    aggregator.apply(
+        &FGGameMessage::LoadedRound(FGRoundInfo::from_str_id("round_tunnel_40")),
+        None,
+    );
+    aggregator.apply(&FGGameMessage::RequestLocalPlayer(3), None);
+    // Other players only count in the qualification.
+    assert!(aggregator.apply(
+        &FGGameMessage::SetPlayerProgress {
+            player_id: 4,
+            is_succeeded: true,
+        },
+        None,
+    ));
+    assert!(aggregator.current().round_result.is_none());
+    assert!(aggregator.apply(
+        &FGGameMessage::SetPlayerProgress {
+            player_id: 3,
+            is_succeeded: true,
+        },
+        None,
+    ));
+
+    let current = aggregator.current();
+    assert_eq!(current.gamemode, Some(FGGameMode::ClassicSolo));
+    assert_eq!(current.round_info.as_ref().unwrap().round_order, 0);
+    assert!(current.round_result.as_ref().unwrap().qualified);
+    assert_eq!(current.qualification.as_ref().unwrap().qualified, 2);
+    let roster = current.roster.as_ref().unwrap();
+    assert_eq!(roster.local_player().unwrap().player_id, 3);
+    assert_eq!(roster.0.len(), 2);
+}
+
+#[test]
+fn aggregate_lobby_platforms() {
+    use crate::models::common::{FGPlatform, FGRoundInfo};
+
+    let mut aggregator = FGCurrentAggregator::new();
+    aggregator.apply(
+        &FGGameMessage::LoadedRound(FGRoundInfo::from_str_id("round_tunnel_40")),
+        None,
+    );
+    for (player_id, platform) in [
+        (1, FGPlatform::PCSteam),
+        (2, FGPlatform::Switch),
+        (1, FGPlatform::PCSteam),
+    ] {
+        aggregator.apply(
+            &FGGameMessage::AppendSpectatorTarget {
+                player_id,
+                squad_id: None,
+                party_id: None,
+                platform,
+            },
+            None,
+        );
+    }
+
+    let platforms = &aggregator.current().lobby_info.as_ref().unwrap().platforms;
+    assert_eq!(platforms.get(&FGPlatform::PCSteam), Some(&1));
+    assert_eq!(platforms.get(&FGPlatform::Switch), Some(&1));
+}
@@ -0,0 +1,5 @@
+pub mod current;
+pub mod qualification;
+pub mod roster;
+#[cfg(feature = "tokio")]
+pub mod task_aggregator;
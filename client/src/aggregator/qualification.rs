@@ -0,0 +1,165 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::models::{
+    common::{FGNetPlayerId, FGPlayerId, FGRoundProfile},
+    exports::FGExportsQualification,
+    messages::FGGameMessage,
+};
+
+/// The smallest team qualifying by `squads_qualification_percentage`, the duos have none of
+/// their own so they qualify by the solo one.
+const MIN_SQUAD_SIZE: u32 = 3;
+
+/// Counts the players qualified and eliminated in the current round.
+#[derive(Debug, Clone, Default)]
+pub struct FGQualificationTracker {
+    /// In percent of the lobby, unknown if the round is not in the data.
+    percentage: Option<isize>,
+    /// The bootstrapped players, keyed by their net ID for the unspawns.
+    players: HashMap<FGNetPlayerId, FGPlayerId>,
+    succeeded: HashSet<FGPlayerId>,
+    failed: HashSet<FGPlayerId>,
+    unspawned: HashSet<FGPlayerId>,
+    /// The qualified count from the server, which includes the players without a progress message.
+    achieving_objective: usize,
+}
+
+impl FGQualificationTracker {
+    /// Starts a new round with the team size of the show, the squads have their own percentage.
+    pub fn start_round(profile: Option<&FGRoundProfile>, team_size: u32) -> Self {
+        let percentage = profile.and_then(|profile| {
+            if team_size >= MIN_SQUAD_SIZE {
+                profile
+                    .squads_qualification_percentage
+                    .or(profile.qualification_percentage)
+            } else {
+                profile.qualification_percentage
+            }
+        });
+        Self {
+            percentage,
+            ..Default::default()
+        }
+    }
+
+    /// Applies the message, returns true if it has been counted.
+    pub fn apply(&mut self, message: &FGGameMessage) -> bool {
+        match message {
+            FGGameMessage::HandleRemotePlayer {
+                player_id,
+                net_player_id,
+                ..
+            }
+            | FGGameMessage::HandleLocalPlayer {
+                player_id,
+                net_player_id,
+                ..
+            } => self.players.insert(*net_player_id, *player_id) != Some(*player_id),
+            FGGameMessage::SetPlayerProgress {
+                player_id,
+                is_succeeded: true,
+            } => {
+                self.failed.remove(player_id);
+                self.succeeded.insert(*player_id)
+            }
+            FGGameMessage::SetPlayerProgress {
+                player_id,
+                is_succeeded: false,
+            } => {
+                self.succeeded.remove(player_id);
+                self.failed.insert(*player_id)
+            }
+            FGGameMessage::HandleUnspawn(net_player_id) => self
+                .players
+                .get(net_player_id)
+                .is_some_and(|player_id| self.unspawned.insert(*player_id)),
+            FGGameMessage::SetNumPlayersAchievingObjective(num_players) => {
+                let num_players = usize::try_from(*num_players).unwrap_or(0);
+                let changed = self.achieving_objective != num_players;
+                self.achieving_objective = num_players;
+                changed
+            }
+            _ => false,
+        }
+    }
+
+    pub fn qualification(&self) -> FGExportsQualification {
+        let lobby_size = self.players.len();
+        let qualified = self.achieving_objective.max(self.succeeded.len());
+        // The unspawned players left the round, but the qualified ones only known from the
+        // server count may be among them.
+        let unknown_qualified = qualified - self.succeeded.len();
+        let gone = self
+            .failed
+            .union(&self.unspawned)
+            .filter(|player_id| !self.succeeded.contains(player_id))
+            .count();
+        let eliminated = gone.saturating_sub(unknown_qualified);
+        // Rounded down, the game never qualifies more than the percentage.
+        let slots = self.percentage.map(|percentage| {
+            lobby_size * usize::try_from(percentage.clamp(0, 100)).unwrap() / 100
+        });
+
+        FGExportsQualification {
+            lobby_size,
+            slots,
+            qualified,
+            eliminated,
+            remaining: slots.map(|slots| slots.saturating_sub(qualified)),
+        }
+    }
+}
+
+#[test]
+fn count_qualification_slots() {
+    use crate::models::common::FGRoundArchetype;
+
+    let profile = FGRoundProfile {
+        archetype: FGRoundArchetype::Race,
+        time_limit: Some(180),
+        end_condition: "success_quota".to_owned(),
+        qualification_percentage: Some(70),
+        squads_qualification_percentage: Some(50),
+        team_mode: "solo".to_owned(),
+        team_count: None,
+        is_scoring: false,
+        is_final: false,
+    };
+    let mut tracker = FGQualificationTracker::start_round(Some(&profile), 1);
+    for player_id in 1..=10 {
+        tracker.apply(&FGGameMessage::HandleRemotePlayer {
+            player_id,
+            net_player_id: player_id + 100,
+            squad_id: None,
+        });
+    }
+    tracker.apply(&FGGameMessage::SetPlayerProgress {
+        player_id: 1,
+        is_succeeded: true,
+    });
+    tracker.apply(&FGGameMessage::SetNumPlayersAchievingObjective(3));
+    // Assumed to be the two qualified without a progress message.
+    tracker.apply(&FGGameMessage::HandleUnspawn(102));
+    tracker.apply(&FGGameMessage::HandleUnspawn(103));
+    tracker.apply(&FGGameMessage::SetPlayerProgress {
+        player_id: 4,
+        is_succeeded: false,
+    });
+    assert!(!tracker.apply(&FGGameMessage::HandleUnspawn(999)));
+
+    assert_eq!(
+        tracker.qualification(),
+        FGExportsQualification {
+            lobby_size: 10,
+            slots: Some(7),
+            qualified: 3,
+            eliminated: 1,
+            remaining: Some(4),
+        }
+    );
+    let percentage =
+        |team_size| FGQualificationTracker::start_round(Some(&profile), team_size).percentage;
+    assert_eq!(percentage(2), Some(70));
+    assert_eq!(percentage(3), Some(50));
+    assert_eq!(percentage(4), Some(50));
+}
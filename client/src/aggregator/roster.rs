@@ -0,0 +1,207 @@
+use std::collections::{BTreeMap, HashMap, btree_map::Entry};
+
+use crate::models::{
+    common::{FGNetPlayerId, FGPlayerId},
+    exports::{FGExportsRoster, FGExportsRosterPlayer, FGRosterSpawnState},
+    messages::FGGameMessage,
+};
+
+/// Reconciles the players of the current round from the messages with either of their IDs.
+#[derive(Debug, Clone, Default)]
+pub struct FGRosterTracker {
+    players: BTreeMap<FGPlayerId, FGExportsRosterPlayer>,
+    /// The net IDs are only known from the bootstraps and the spawns.
+    player_ids: HashMap<FGNetPlayerId, FGPlayerId>,
+    local_player_id: Option<FGPlayerId>,
+}
+
+impl FGRosterTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Applies the message, returns true if any player has been changed.
+    pub fn apply(&mut self, message: &FGGameMessage) -> bool {
+        match message {
+            FGGameMessage::RequestLocalPlayer(player_id) => {
+                self.local_player_id = Some(*player_id);
+                self.update(*player_id, |player| player.is_local = true)
+            }
+            FGGameMessage::HandleLocalPlayer {
+                player_id,
+                net_player_id,
+                squad_id,
+            } => {
+                self.local_player_id = Some(*player_id);
+                self.link(*player_id, *net_player_id);
+                self.update(*player_id, |player| {
+                    player.is_local = true;
+                    player.net_player_id = Some(*net_player_id);
+                    player.squad_id = squad_id.or(player.squad_id);
+                })
+            }
+            FGGameMessage::HandleRemotePlayer {
+                player_id,
+                net_player_id,
+                squad_id,
+            } => {
+                self.link(*player_id, *net_player_id);
+                self.update(*player_id, |player| {
+                    player.net_player_id = Some(*net_player_id);
+                    player.squad_id = squad_id.or(player.squad_id);
+                })
+            }
+            FGGameMessage::PlayerSpawned {
+                player_id,
+                net_player_id,
+            } => {
+                self.link(*player_id, *net_player_id);
+                self.update(*player_id, |player| {
+                    player.net_player_id = Some(*net_player_id);
+                    player.spawn_state = FGRosterSpawnState::Spawned;
+                })
+            }
+            FGGameMessage::AppendSpectatorTarget {
+                player_id,
+                squad_id,
+                party_id,
+                platform,
+            } => self.update(*player_id, |player| {
+                player.squad_id = squad_id.or(player.squad_id);
+                player.party_id = party_id.or(player.party_id);
+                player.platform = Some(platform.clone());
+            }),
+            FGGameMessage::SetLocalSquadId(squad_id) => match self.local_player_id {
+                Some(player_id) => self.update(player_id, |player| player.squad_id = *squad_id),
+                None => false,
+            },
+            FGGameMessage::SetLocalPartyId(party_id) => match self.local_player_id {
+                Some(player_id) => self.update(player_id, |player| player.party_id = *party_id),
+                None => false,
+            },
+            FGGameMessage::SetPlayerScore {
+                net_player_id,
+                score,
+            } => self.update_net(*net_player_id, |player| player.score = Some(*score)),
+            FGGameMessage::HandleUnspawn(net_player_id) => {
+                self.update_net(*net_player_id, |player| {
+                    player.spawn_state = FGRosterSpawnState::Unspawned;
+                })
+            }
+            FGGameMessage::SetPlayerProgress {
+                player_id,
+                is_succeeded,
+            } => self.update(*player_id, |player| {
+                player.is_succeeded = Some(*is_succeeded);
+            }),
+            _ => false,
+        }
+    }
+
+    pub fn roster(&self) -> FGExportsRoster {
+        FGExportsRoster(self.players.values().cloned().collect())
+    }
+
+    fn link(&mut self, player_id: FGPlayerId, net_player_id: FGNetPlayerId) {
+        // The previous net ID of the player is stale, e.g. respawned with a new object.
+        if let Some(previous) = self.players.get(&player_id).and_then(|p| p.net_player_id) {
+            self.player_ids.remove(&previous);
+        }
+        self.player_ids.insert(net_player_id, player_id);
+    }
+
+    /// Adds the player if not known yet.
+    fn update(
+        &mut self,
+        player_id: FGPlayerId,
+        update: impl FnOnce(&mut FGExportsRosterPlayer),
+    ) -> bool {
+        let is_local = self.local_player_id == Some(player_id);
+        let player = self.players.entry(player_id);
+        let is_new = matches!(player, Entry::Vacant(_));
+        let player = player.or_insert_with(|| FGExportsRosterPlayer {
+            player_id,
+            net_player_id: None,
+            is_local,
+            squad_id: None,
+            party_id: None,
+            platform: None,
+            spawn_state: FGRosterSpawnState::default(),
+            score: None,
+            is_succeeded: None,
+        });
+        let before = player.clone();
+        update(player);
+        is_new || *player != before
+    }
+
+    /// Ignored if the net ID has not been bootstrapped.
+    fn update_net(
+        &mut self,
+        net_player_id: FGNetPlayerId,
+        update: impl FnOnce(&mut FGExportsRosterPlayer),
+    ) -> bool {
+        match self.player_ids.get(&net_player_id) {
+            Some(&player_id) => self.update(player_id, update),
+            None => false,
+        }
+    }
+}
+
+#[test]
+fn reconcile_player_ids() {
+    use crate::models::common::FGPlatform;
+
+    let mut tracker = FGRosterTracker::new();
+    for message in [
+        FGGameMessage::RequestLocalPlayer(21),
+        FGGameMessage::HandleLocalPlayer {
+            player_id: 21,
+            net_player_id: 830,
+            squad_id: Some(6),
+        },
+        FGGameMessage::SetLocalPartyId(Some(1187)),
+        FGGameMessage::HandleRemotePlayer {
+            player_id: 3,
+            net_player_id: 701,
+            squad_id: Some(6),
+        },
+        FGGameMessage::AppendSpectatorTarget {
+            player_id: 3,
+            squad_id: Some(6),
+            party_id: None,
+            platform: FGPlatform::Switch,
+        },
+        FGGameMessage::PlayerSpawned {
+            player_id: 3,
+            net_player_id: 701,
+        },
+        FGGameMessage::SetPlayerScore {
+            net_player_id: 701,
+            score: 4,
+        },
+        FGGameMessage::HandleUnspawn(701),
+        FGGameMessage::SetPlayerProgress {
+            player_id: 21,
+            is_succeeded: true,
+        },
+    ] {
+        assert!(tracker.apply(&message), "{:?}", message);
+    }
+    // Not bootstrapped.
+    assert!(!tracker.apply(&FGGameMessage::HandleUnspawn(999)));
+
+    let roster = tracker.roster();
+    let local = roster.local_player().unwrap();
+    assert_eq!(
+        (local.player_id, local.net_player_id, local.party_id),
+        (21, Some(830), Some(1187))
+    );
+    assert_eq!(local.is_succeeded, Some(true));
+    let remote = roster.net_player(701).unwrap();
+    assert_eq!(roster.player(3), Some(remote));
+    assert_eq!(remote.platform, Some(FGPlatform::Switch));
+    assert_eq!(remote.score, Some(4));
+    assert_eq!(remote.spawn_state, FGRosterSpawnState::Unspawned);
+    assert!(!remote.is_local);
+}
@@ -0,0 +1,37 @@
+use temporal_rs::Instant;
+use tokio::sync::{
+    mpsc::{self, Receiver},
+    watch,
+};
+
+use crate::models::{exports::FGExportsCurrent, messages::FGGameMessage};
+
+use super::current::FGCurrentAggregator;
+
+/// Keeps the `FGExportsCurrent` up to date from the parsed messages.
+///
+/// Returns the snapshot subscriber, and the messages passed through as-is for the next consumer.
+pub async fn aggregate_from_rx(
+    mut message_rx: Receiver<(FGGameMessage, Option<Instant>)>,
+) -> (
+    watch::Receiver<FGExportsCurrent>,
+    Receiver<(FGGameMessage, Option<Instant>)>,
+) {
+    let (current_tx, current_rx) = watch::channel(FGExportsCurrent::default());
+    let (tx, rx) = mpsc::channel(1024);
+
+    tokio::spawn(async move {
+        let mut aggregator = FGCurrentAggregator::new();
+        while let Some((message, time)) = message_rx.recv().await {
+            if aggregator.apply(&message, time.as_ref()) {
+                current_tx.send_replace(aggregator.current().clone());
+            }
+
+            if tx.send((message, time)).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    (current_rx, rx)
+}
@@ -0,0 +1,72 @@
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand, ValueEnum};
+
+use crate::config::FGStdoutFormat;
+
+/// Fall Guys telemetry from the game log.
+#[derive(Debug, Parser)]
+#[command(version)]
+pub struct FGCli {
+    /// The configuration file, overrides `CONFIG_FILE`.
+    #[arg(long, global = true)]
+    pub config: Option<PathBuf>,
+    /// `watch` if not given.
+    #[command(subcommand)]
+    pub command: Option<FGCommand>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum FGCommand {
+    /// Keep watching the live log file.
+    Watch {
+        /// Overrides `output.stdout.format`.
+        #[arg(long)]
+        format: Option<FGStdoutFormat>,
+    },
+    /// Replay the log files in order (e.g. `Player-prev.log Player.log`), then exit.
+    Replay {
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+        /// Overrides `output.stdout.format`.
+        #[arg(long)]
+        format: Option<FGStdoutFormat>,
+    },
+    /// Summarize the episodes in the history database.
+    Stats {
+        /// Only the episodes started in the last N days, all of them if not given.
+        #[arg(long)]
+        days: Option<u32>,
+        #[arg(long, value_enum, default_value_t = FGReportFormat::Text)]
+        format: FGReportFormat,
+    },
+    /// Export the episodes in the history database.
+    Export {
+        /// Only the episodes started in the last N days, all of them if not given.
+        #[arg(long)]
+        days: Option<u32>,
+        #[arg(long, value_enum, default_value_t = FGExportFormat::Csv)]
+        format: FGExportFormat,
+        /// Written to stdout if not given.
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// List the registered parser rules with their anchors.
+    Rules,
+    /// Validate the extra data, exits with 1 on problems.
+    CheckAssets,
+    /// Print the configuration after the environment variables.
+    PrintConfig,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum FGReportFormat {
+    Text,
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum FGExportFormat {
+    Csv,
+    Json,
+}
@@ -0,0 +1,176 @@
+//! The one-shot commands, which do not run the pipeline.
+
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+    process::ExitCode,
+};
+
+use temporal_rs::Temporal;
+use tracing::error;
+
+use crate::{
+    cli::{FGExportFormat, FGReportFormat},
+    config::FGConfig,
+    extra_data::{self, extra_data_assets},
+    history::{export::write_csv, stats::FGHistoryStats, store::FGHistoryStore},
+    models::history::FGHistoryEpisode,
+    parser::rules::rules,
+};
+
+const DAY_MILLIS: i64 = 24 * 60 * 60 * 1000;
+
+pub fn print_rules() -> ExitCode {
+    for rule in rules() {
+        println!("{}\t{:?}", rule.name, rule.anchor);
+    }
+    ExitCode::SUCCESS
+}
+
+pub fn print_config(config: &FGConfig) -> ExitCode {
+    print!("{}", config.to_toml());
+    ExitCode::SUCCESS
+}
+
+pub fn check_assets(config: &FGConfig) -> ExitCode {
+    if let Err(err) =
+        extra_data::load_extra_data_assets(config.extra_data.dir.as_deref(), &config.locale)
+    {
+        println!("{}", err);
+        return ExitCode::FAILURE;
+    }
+    let assets = &*extra_data_assets();
+    println!(
+        "{} game rules, {} rounds, {} strings, {} shows",
+        assets.game_rules.len(),
+        assets.levels_round.len(),
+        assets.localized_strings.len(),
+        assets.shows.len()
+    );
+
+    let problems = extra_data::check_extra_data_assets(assets);
+    for problem in &problems {
+        println!("{}", problem);
+    }
+    if problems.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        println!("{} problems found", problems.len());
+        ExitCode::FAILURE
+    }
+}
+
+pub fn print_stats(config: &FGConfig, days: Option<u32>, format: FGReportFormat) -> ExitCode {
+    if !load_assets(config) {
+        return ExitCode::FAILURE;
+    }
+    let Some(episodes) = read_episodes(config, days) else {
+        return ExitCode::FAILURE;
+    };
+    // The system one is used if not set.
+    let time_zone = match config.time_zone() {
+        Ok(Some(time_zone)) => time_zone,
+        Ok(None) => match Temporal::now().zoned_date_time_iso(None) {
+            Ok(now) => now.timezone().clone(),
+            Err(err) => {
+                error!("Could not get the system time zone: {}", err);
+                return ExitCode::FAILURE;
+            }
+        },
+        Err(err) => {
+            error!("Invalid configuration: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let stats = FGHistoryStats::from_episodes(&episodes, &time_zone);
+    match format {
+        FGReportFormat::Text => print!("{}", stats),
+        FGReportFormat::Json => match serde_json::to_string_pretty(&stats) {
+            Ok(json) => println!("{}", json),
+            Err(err) => {
+                error!("Could not serialize the stats: {}", err);
+                return ExitCode::FAILURE;
+            }
+        },
+    }
+    ExitCode::SUCCESS
+}
+
+pub fn export(
+    config: &FGConfig,
+    days: Option<u32>,
+    format: FGExportFormat,
+    output: Option<PathBuf>,
+) -> ExitCode {
+    if !load_assets(config) {
+        return ExitCode::FAILURE;
+    }
+    let Some(episodes) = read_episodes(config, days) else {
+        return ExitCode::FAILURE;
+    };
+    let writer: Box<dyn Write> = match &output {
+        Some(path) => match File::create(path) {
+            Ok(file) => Box::new(file),
+            Err(err) => {
+                error!("Could not create {}: {}", path.display(), err);
+                return ExitCode::FAILURE;
+            }
+        },
+        None => Box::new(io::stdout().lock()),
+    };
+    let mut writer = BufWriter::new(writer);
+
+    let result = match format {
+        FGExportFormat::Csv => write_csv(&episodes, &mut writer),
+        FGExportFormat::Json => serde_json::to_writer_pretty(&mut writer, &episodes)
+            .map_err(io::Error::from)
+            .and_then(|_| writeln!(writer)),
+    };
+    match result.and_then(|_| writer.flush()) {
+        Ok(_) => ExitCode::SUCCESS,
+        Err(err) => {
+            error!("Could not export the episodes: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// The shows and the rounds of the history are resolved from the configured data, like the pipeline.
+fn load_assets(config: &FGConfig) -> bool {
+    match extra_data::load_extra_data_assets(config.extra_data.dir.as_deref(), &config.locale) {
+        Ok(()) => true,
+        Err(err) => {
+            error!("Could not load the extra data: {}", err);
+            false
+        }
+    }
+}
+
+/// Reads the episodes started in the last `days`, or all of them.
+fn read_episodes(config: &FGConfig, days: Option<u32>) -> Option<Vec<FGHistoryEpisode>> {
+    let path = &config.output.history.path;
+    // Opening creates the database, which is not wanted for reading.
+    if !path.is_file() {
+        error!("No history database at {}", path.display());
+        return None;
+    }
+    let from = match days {
+        Some(days) => match Temporal::now().instant() {
+            Ok(now) => now.epoch_milliseconds() - i64::from(days) * DAY_MILLIS,
+            Err(err) => {
+                error!("Could not get the current time: {}", err);
+                return None;
+            }
+        },
+        None => i64::MIN,
+    };
+
+    match FGHistoryStore::open(path).and_then(|store| store.episodes_between(from, i64::MAX)) {
+        Ok(episodes) => Some(episodes),
+        Err(err) => {
+            error!("Could not read the history: {}", err);
+            None
+        }
+    }
+}
@@ -0,0 +1,437 @@
+//! The TOML configuration file, overridden by the environment variables.
+
+use std::{
+    env,
+    fmt::Display,
+    fs,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_with::{DeserializeFromStr, SerializeDisplay};
+use temporal_rs::TimeZone;
+
+use crate::{
+    discovery::DEFAULT_LOG_FILE, extra_data::DEFAULT_LOCALE, parser::task_watch::FGWatchMode,
+};
+
+/// Read from the working directory if it exists, unless `CONFIG_FILE` is set.
+pub const DEFAULT_CONFIG_FILE: &str = "config.toml";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FGConfig {
+    /// The time zone of the log times (e.g. `Asia/Seoul`), the local one if not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_zone: Option<String>,
+    /// The locale of the localized round and show names.
+    pub locale: String,
+    pub log: FGLogConfig,
+    pub extra_data: FGExtraDataConfig,
+    pub output: FGOutputConfig,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FGLogConfig {
+    /// The candidate log directories, the one with the latest log file is watched.
+    /// Found from the Steam, Heroic and Lutris prefixes if empty.
+    pub dirs: Vec<PathBuf>,
+    pub file: String,
+    pub watch_mode: FGWatchMode,
+    pub poll_interval_ms: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FGExtraDataConfig {
+    /// The files of the same name in this directory override the embedded ones.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FGOutputConfig {
+    pub stdout: FGStdoutOutput,
+    pub http: FGHttpOutput,
+    pub history: FGHistoryOutput,
+    /// POSTed every lifecycle event as JSON.
+    pub webhooks: Vec<FGWebhookOutput>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FGStdoutOutput {
+    pub enabled: bool,
+    pub format: FGStdoutFormat,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, SerializeDisplay, DeserializeFromStr)]
+pub enum FGStdoutFormat {
+    /// The Rust debug representation of the messages.
+    #[default]
+    Debug,
+    /// One `FGVersioned<FGTimedMessage>` per line.
+    Json,
+    /// `FGVersioned<FGTimedMessage>` in the indented JSON.
+    Pretty,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FGHttpOutput {
+    pub enabled: bool,
+    pub address: SocketAddr,
+    /// The number of messages kept for `/events`.
+    pub recent_events: usize,
+    /// Serves `/ws` along with the JSON API.
+    pub websocket: bool,
+    /// The browser origins allowed to `GET` the API, e.g. `http://localhost:3000`.
+    /// `*` allows any origin, e.g. for the OBS browser sources.
+    pub cors_origins: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FGHistoryOutput {
+    pub enabled: bool,
+    pub path: PathBuf,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FGWebhookOutput {
+    pub url: String,
+}
+
+impl Default for FGConfig {
+    fn default() -> Self {
+        Self {
+            time_zone: None,
+            locale: DEFAULT_LOCALE.to_owned(),
+            log: FGLogConfig::default(),
+            extra_data: FGExtraDataConfig::default(),
+            output: FGOutputConfig::default(),
+        }
+    }
+}
+
+impl Default for FGLogConfig {
+    fn default() -> Self {
+        Self {
+            dirs: Vec::new(),
+            file: DEFAULT_LOG_FILE.to_owned(),
+            watch_mode: FGWatchMode::default(),
+            poll_interval_ms: 1000,
+        }
+    }
+}
+
+impl Default for FGStdoutOutput {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            format: FGStdoutFormat::default(),
+        }
+    }
+}
+
+impl Default for FGHttpOutput {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            address: SocketAddr::from(([127, 0, 0, 1], 8686)),
+            recent_events: 256,
+            websocket: true,
+            cors_origins: vec!["*".to_owned()],
+        }
+    }
+}
+
+impl Default for FGHistoryOutput {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            path: PathBuf::from("history.sqlite3"),
+        }
+    }
+}
+
+impl std::str::FromStr for FGStdoutFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "debug" => Ok(FGStdoutFormat::Debug),
+            "json" => Ok(FGStdoutFormat::Json),
+            "pretty" => Ok(FGStdoutFormat::Pretty),
+            _ => Err(format!(
+                "unknown format `{}`, expected debug, json or pretty",
+                s
+            )),
+        }
+    }
+}
+
+impl Display for FGStdoutFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let format = match self {
+            FGStdoutFormat::Debug => "debug",
+            FGStdoutFormat::Json => "json",
+            FGStdoutFormat::Pretty => "pretty",
+        };
+        write!(f, "{}", format)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FGConfigError {
+    Read {
+        path: PathBuf,
+        error: String,
+    },
+    /// The TOML syntax or the types, the error shows the offending line.
+    Parse {
+        path: PathBuf,
+        error: String,
+    },
+    Env {
+        name: &'static str,
+        value: String,
+        reason: String,
+    },
+    Invalid {
+        field: &'static str,
+        reason: String,
+    },
+}
+
+impl Display for FGConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FGConfigError::Read { path, error } => {
+                write!(f, "could not read {}: {}", path.display(), error)
+            }
+            FGConfigError::Parse { path, error } => {
+                write!(f, "invalid {}:\n{}", path.display(), error)
+            }
+            FGConfigError::Env {
+                name,
+                value,
+                reason,
+            } => write!(f, "invalid `{}={}`: {}", name, value, reason),
+            FGConfigError::Invalid { field, reason } => {
+                write!(f, "invalid `{}`: {}", field, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FGConfigError {}
+
+impl FGConfig {
+    /// Reads the given file, `CONFIG_FILE` or `config.toml`, then applies the environment variables.
+    ///
+    /// Missing `config.toml` is not an error, while the missing given file is.
+    pub fn load(path: Option<PathBuf>) -> Result<Self, FGConfigError> {
+        let (path, required) =
+            match path.or_else(|| env::var("CONFIG_FILE").ok().map(PathBuf::from)) {
+                Some(path) => (path, true),
+                None => (PathBuf::from(DEFAULT_CONFIG_FILE), false),
+            };
+        let mut config = match fs::read_to_string(&path) {
+            Ok(text) => Self::parse(&text, &path)?,
+            Err(_) if !required && !path.exists() => Self::default(),
+            Err(err) => {
+                return Err(FGConfigError::Read {
+                    path,
+                    error: err.to_string(),
+                });
+            }
+        };
+        config.apply_env(|name| env::var(name).ok())?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn parse(text: &str, path: &Path) -> Result<Self, FGConfigError> {
+        toml::from_str(text).map_err(|err| FGConfigError::Parse {
+            path: path.to_owned(),
+            error: err.to_string(),
+        })
+    }
+
+    /// Overrides the fields with the environment variables kept from the `.env` days.
+    pub fn apply_env(&mut self, var: impl Fn(&str) -> Option<String>) -> Result<(), FGConfigError> {
+        fn parse<T: std::str::FromStr>(
+            name: &'static str,
+            value: String,
+        ) -> Result<T, FGConfigError>
+        where
+            T::Err: Display,
+        {
+            value.parse().map_err(|err: T::Err| FGConfigError::Env {
+                name,
+                reason: err.to_string(),
+                value,
+            })
+        }
+
+        if let Some(dir) = var("LOG_DIR") {
+            self.log.dirs = vec![PathBuf::from(dir)];
+        }
+        if let Some(file) = var("LOG_FILE") {
+            self.log.file = file;
+        }
+        if let Some(mode) = var("WATCH_MODE") {
+            self.log.watch_mode = parse("WATCH_MODE", mode)?;
+        }
+        if let Some(millis) = var("POLL_INTERVAL_MS") {
+            self.log.poll_interval_ms = parse("POLL_INTERVAL_MS", millis)?;
+        }
+        if let Some(time_zone) = var("TIME_ZONE") {
+            self.time_zone = Some(time_zone);
+        }
+        if let Some(locale) = var("LOCALE") {
+            self.locale = locale;
+        }
+        if let Some(dir) = var("EXTRA_DATA_DIR") {
+            self.extra_data.dir = Some(PathBuf::from(dir));
+        }
+        if let Some(path) = var("HISTORY_DB") {
+            self.output.history.path = PathBuf::from(path);
+        }
+        if let Some(port) = var("HTTP_PORT") {
+            self.output.http.address.set_port(parse("HTTP_PORT", port)?);
+        }
+        Ok(())
+    }
+
+    pub fn validate(&self) -> Result<(), FGConfigError> {
+        let invalid = |field, reason: &str| {
+            Err(FGConfigError::Invalid {
+                field,
+                reason: reason.to_owned(),
+            })
+        };
+
+        self.time_zone()?;
+        if self.locale.is_empty() {
+            return invalid("locale", "must not be empty");
+        }
+        if self.log.file.is_empty() || self.log.file.contains(['/', '\\']) {
+            return invalid("log.file", "must be a file name without the directory");
+        }
+        if self.log.poll_interval_ms == 0 {
+            return invalid("log.poll_interval_ms", "must be greater than 0");
+        }
+        if let Some(dir) = &self.extra_data.dir
+            && !dir.is_dir()
+        {
+            return invalid(
+                "extra_data.dir",
+                &format!("{} is not a directory", dir.display()),
+            );
+        }
+        if self.output.http.recent_events == 0 {
+            return invalid("output.http.recent_events", "must be greater than 0");
+        }
+        for origin in &self.output.http.cors_origins {
+            if origin != "*" && axum::http::HeaderValue::from_str(origin).is_err() {
+                return invalid(
+                    "output.http.cors_origins",
+                    &format!("{} is not a valid origin", origin),
+                );
+            }
+        }
+        if self.output.history.enabled && self.output.history.path.as_os_str().is_empty() {
+            return invalid("output.history.path", "must not be empty");
+        }
+        for webhook in &self.output.webhooks {
+            match reqwest::Url::parse(&webhook.url) {
+                Ok(url) if matches!(url.scheme(), "http" | "https") => {}
+                Ok(_) => return invalid("output.webhooks.url", "must be a http(s) URL"),
+                Err(err) => {
+                    return invalid("output.webhooks.url", &format!("{}: {}", webhook.url, err));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn time_zone(&self) -> Result<Option<TimeZone>, FGConfigError> {
+        let Some(time_zone) = &self.time_zone else {
+            return Ok(None);
+        };
+        let invalid = |reason: String| FGConfigError::Invalid {
+            field: "time_zone",
+            reason,
+        };
+        let time_zone =
+            TimeZone::try_from_str(time_zone).map_err(|err| invalid(err.to_string()))?;
+        // Only the syntax is checked on parse, the IANA names are looked up here.
+        if !time_zone.is_valid() {
+            return Err(invalid("unknown time zone".to_owned()));
+        }
+        Ok(Some(time_zone))
+    }
+
+    /// The effective configuration in TOML, for `print-config`.
+    pub fn to_toml(&self) -> String {
+        toml::to_string_pretty(self).unwrap()
+    }
+}
+
+#[test]
+fn parse_config_with_env() {
+    let path = Path::new("config.toml");
+    let mut config = FGConfig::parse(
+        r#"
+        time_zone = "Asia/Seoul"
+
+        [log]
+        watch_mode = "poll"
+
+        [output.http]
+        websocket = false
+        cors_origins = ["http://localhost:3000"]
+
+        [[output.webhooks]]
+        url = "https://example.com/hook"
+        "#,
+        path,
+    )
+    .unwrap();
+    config
+        .apply_env(|name| (name == "HTTP_PORT").then(|| "18686".to_owned()))
+        .unwrap();
+    config.validate().unwrap();
+    assert_eq!(config.log.watch_mode, FGWatchMode::Poll);
+    assert_eq!(config.log.file, DEFAULT_LOG_FILE);
+    assert_eq!(config.output.http.address.port(), 18686);
+    assert!(!config.output.http.websocket);
+    assert_eq!(config.output.http.cors_origins, ["http://localhost:3000"]);
+    assert_eq!(FGConfig::parse(&config.to_toml(), path).unwrap(), config);
+
+    assert!(matches!(
+        FGConfig::parse("[log]\nwatch_mod = \"poll\"", path),
+        Err(FGConfigError::Parse { .. })
+    ));
+    assert!(matches!(
+        config.apply_env(|name| (name == "WATCH_MODE").then(|| "inotify".to_owned())),
+        Err(FGConfigError::Env {
+            name: "WATCH_MODE",
+            ..
+        })
+    ));
+    config.time_zone = Some("Mars/Olympus".to_owned());
+    assert!(matches!(
+        config.validate(),
+        Err(FGConfigError::Invalid {
+            field: "time_zone",
+            ..
+        })
+    ));
+}
@@ -0,0 +1,222 @@
+//! Finds the Fall Guys log directory in the known Wine/Proton prefixes on Linux.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use crate::parser::create_regex;
+
+/// The Steam app id of Fall Guys.
+const STEAM_APP_ID: &str = "1097150";
+/// The log directory relative to the Windows user directory in the prefix.
+const LOG_DIR_IN_USER: &str = "AppData/LocalLow/Mediatonic/FallGuys_client";
+pub const DEFAULT_LOG_FILE: &str = "Player.log";
+
+/// Steam installations relative to the home directory, including Flatpak and Snap.
+const STEAM_ROOTS: &[&str] = &[
+    ".steam/steam",
+    ".local/share/Steam",
+    ".var/app/com.valvesoftware.Steam/.local/share/Steam",
+    "snap/steam/common/.local/share/Steam",
+];
+const HEROIC_CONFIG_DIRS: &[&str] = &[
+    ".config/heroic/GamesConfig",
+    ".var/app/com.heroicgameslauncher.hgl/config/heroic/GamesConfig",
+];
+const HEROIC_PREFIX_DIRS: &[&str] = &["Games/Heroic/Prefixes", "Games/Heroic/Prefixes/default"];
+const LUTRIS_CONFIG_DIRS: &[&str] = &[".config/lutris/games", ".local/share/lutris/games"];
+/// Lutris creates the prefixes here by default.
+const LUTRIS_PREFIX_DIR: &str = "Games";
+
+/// Where the prefix has been found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FGLogSource {
+    Steam,
+    Heroic,
+    Lutris,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FGLogLocation {
+    pub dir: PathBuf,
+    pub source: FGLogSource,
+    /// The last modified time of the log file, None if it has not been created yet.
+    pub modified: Option<SystemTime>,
+}
+
+/// Lists the existing log directories under the home directory, without duplicates.
+pub fn discover_log_dirs(home: &Path, file_name: &str) -> Vec<FGLogLocation> {
+    let prefixes = steam_prefixes(home)
+        .into_iter()
+        .map(|prefix| (prefix, FGLogSource::Steam))
+        .chain(
+            heroic_prefixes(home)
+                .into_iter()
+                .map(|prefix| (prefix, FGLogSource::Heroic)),
+        )
+        .chain(
+            lutris_prefixes(home)
+                .into_iter()
+                .map(|prefix| (prefix, FGLogSource::Lutris)),
+        );
+
+    let mut locations: Vec<FGLogLocation> = Vec::new();
+    for (prefix, source) in prefixes {
+        for dir in log_dirs_in_prefix(&prefix) {
+            // The same prefix can be reached from the symlinked Steam roots.
+            let dir = fs::canonicalize(&dir).unwrap_or(dir);
+            if locations.iter().any(|location| location.dir == dir) {
+                continue;
+            }
+            let modified = fs::metadata(dir.join(file_name))
+                .and_then(|metadata| metadata.modified())
+                .ok();
+            locations.push(FGLogLocation {
+                dir,
+                source,
+                modified,
+            });
+        }
+    }
+    locations
+}
+
+/// Picks the directory of the most recently written log file, which should be the active one.
+pub fn find_log_dir(home: &Path, file_name: &str) -> Option<FGLogLocation> {
+    discover_log_dirs(home, file_name)
+        .into_iter()
+        .max_by_key(|location| location.modified)
+}
+
+/// Picks the configured directory of the most recently written log file, the first one if none
+/// has been written yet.
+pub fn latest_log_dir<'a>(dirs: &'a [PathBuf], file_name: &str) -> Option<&'a PathBuf> {
+    dirs.iter().rev().max_by_key(|dir| {
+        fs::metadata(dir.join(file_name))
+            .and_then(|metadata| metadata.modified())
+            .ok()
+    })
+}
+
+fn steam_prefixes(home: &Path) -> Vec<PathBuf> {
+    let mut libraries = Vec::new();
+    for root in STEAM_ROOTS {
+        let root = home.join(root);
+        if !root.is_dir() {
+            continue;
+        }
+        if let Ok(vdf) = fs::read_to_string(root.join("steamapps/libraryfolders.vdf")) {
+            libraries.extend(library_folders(&vdf));
+        }
+        libraries.push(root);
+    }
+
+    libraries
+        .into_iter()
+        .map(|library| {
+            library
+                .join("steamapps/compatdata")
+                .join(STEAM_APP_ID)
+                .join("pfx")
+        })
+        .collect()
+}
+
+/// Parses the library paths from `libraryfolders.vdf`.
+fn library_folders(vdf: &str) -> Vec<PathBuf> {
+    create_regex(r#""path"\s+"(?<path>(?:[^"\\]|\\.)*)""#)
+        .captures_iter(vdf)
+        .map(|caps| PathBuf::from(caps["path"].replace(r"\\", r"\")))
+        .collect()
+}
+
+fn heroic_prefixes(home: &Path) -> Vec<PathBuf> {
+    let mut prefixes = Vec::new();
+    for config_dir in HEROIC_CONFIG_DIRS {
+        for config in read_dir_paths(&home.join(config_dir)) {
+            let Ok(text) = fs::read_to_string(&config) else {
+                continue;
+            };
+            let Ok(json) = serde_json::from_str::<serde_json::Value>(&text) else {
+                continue;
+            };
+            // `{"<app name>": {"winePrefix": "...", ...}, "version": "v0"}`
+            if let Some(games) = json.as_object() {
+                prefixes.extend(
+                    games
+                        .values()
+                        .filter_map(|game| game.get("winePrefix")?.as_str())
+                        .map(PathBuf::from),
+                );
+            }
+        }
+    }
+    for prefix_dir in HEROIC_PREFIX_DIRS {
+        prefixes.extend(read_dir_paths(&home.join(prefix_dir)));
+    }
+    prefixes
+}
+
+fn lutris_prefixes(home: &Path) -> Vec<PathBuf> {
+    let mut prefixes = Vec::new();
+    for config_dir in LUTRIS_CONFIG_DIRS {
+        for config in read_dir_paths(&home.join(config_dir)) {
+            let Ok(text) = fs::read_to_string(&config) else {
+                continue;
+            };
+            prefixes.extend(
+                create_regex(r"(?m)^\s*prefix:\s*(?<prefix>.+?)\s*$")
+                    .captures_iter(&text)
+                    .map(|caps| PathBuf::from(caps["prefix"].trim_matches(['"', '\'']))),
+            );
+        }
+    }
+    prefixes.extend(read_dir_paths(&home.join(LUTRIS_PREFIX_DIR)));
+    prefixes
+}
+
+/// The log directories of every Windows user in the prefix, `steamuser` on Proton.
+fn log_dirs_in_prefix(prefix: &Path) -> Vec<PathBuf> {
+    read_dir_paths(&prefix.join("drive_c/users"))
+        .into_iter()
+        .map(|user| user.join(LOG_DIR_IN_USER))
+        .filter(|dir| dir.is_dir())
+        .collect()
+}
+
+fn read_dir_paths(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries.flatten().map(|entry| entry.path()).collect()
+}
+
+#[test]
+fn discover_steam_library_prefix() {
+    let temp_dir = crate::test_support::FGTempDir::new("discovery");
+    let home = temp_dir.path();
+    let library = home.join("sdcard/SteamLibrary");
+    let log_dir = library
+        .join("steamapps/compatdata/1097150/pfx/drive_c/users/steamuser")
+        .join(LOG_DIR_IN_USER);
+    fs::create_dir_all(&log_dir).unwrap();
+    fs::write(log_dir.join(DEFAULT_LOG_FILE), "").unwrap();
+
+    let steam_apps = home.join(".local/share/Steam/steamapps");
+    fs::create_dir_all(&steam_apps).unwrap();
+    fs::write(
+        steam_apps.join("libraryfolders.vdf"),
+        format!(
+            "\"libraryfolders\"\n{{\n\t\"1\"\n\t{{\n\t\t\"path\"\t\t\"{}\"\n\t}}\n}}\n",
+            library.display()
+        ),
+    )
+    .unwrap();
+
+    let location = find_log_dir(home, DEFAULT_LOG_FILE).unwrap();
+    assert_eq!(location.dir, fs::canonicalize(&log_dir).unwrap());
+    assert_eq!(location.source, FGLogSource::Steam);
+    assert!(location.modified.is_some());
+}
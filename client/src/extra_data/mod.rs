@@ -0,0 +1,469 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::Path,
+    sync::{Arc, LazyLock, RwLock},
+};
+
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use crate::models::common::{FGRoundArchetype, FGRoundProfile, FGShowCategory};
+
+#[cfg(feature = "watch")]
+pub mod task_reload;
+
+/// The assets in use, replaced by `load_extra_data_assets`.
+static EXTRA_DATA_ASSETS: LazyLock<RwLock<Arc<FGExtraDataAssets>>> =
+    LazyLock::new(|| RwLock::new(Arc::new(initialize_extra_data_assets())));
+
+/// The locale of the embedded `localised_strings.json`.
+pub const DEFAULT_LOCALE: &str = "en";
+
+const STR_GAME_RULES: &str = include_str!("../../extra_datas/game_rules.json");
+// Copied by `build.rs`, or empty if it is missing.
+const STR_LEVELS_ROUND: &str = include_str!(concat!(env!("OUT_DIR"), "/levels_round.json"));
+const STR_LOCALIZED_STRINGS: &str = include_str!("../../extra_datas/localised_strings.json");
+const STR_SHOWS: &str = include_str!("../../extra_datas/shows.json");
+const STR_SHOW_TAGS: &str = include_str!("../../extra_datas/show_tags.json");
+
+/// The assets in use, the embedded ones unless loaded from the data directory.
+///
+/// Keep the returned assets only as long as needed, they may have been reloaded since.
+pub fn extra_data_assets() -> Arc<FGExtraDataAssets> {
+    EXTRA_DATA_ASSETS.read().unwrap().clone()
+}
+
+pub struct FGExtraDataAssets {
+    pub game_rules: HashMap<String, FGExtraDataGameRulesItem>,
+    pub levels_round: HashMap<String, FGExtraDataLevelsRoundItem>,
+    pub localized_strings: HashMap<String, String>,
+    pub shows: HashMap<String, FGExtraDataShowsItem>,
+    /// The category of the shows by their `show_tag`.
+    pub show_tags: HashMap<String, FGShowCategory>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FGExtraDataGameRulesItem {
+    pub id: String,
+    pub min_participants: Option<isize>,
+    pub max_participants: Option<isize>,
+    pub min_participants_private_lobby: Option<isize>,
+    pub max_participants_private_lobby: Option<isize>,
+    pub qualification_percentage: Option<isize>,
+    pub squads_qualification_percentage: Option<isize>,
+    pub has_timer: Option<bool>,
+    pub duration: isize,
+    pub time_left_red_alert_threshold: isize,
+    pub game_manager_spawn_type: String,
+    pub round_end_condition: String,
+    pub overtime_mode: String,
+    pub overtime_amount: Option<isize>,
+    pub team_mode: String,
+    pub team_count: Option<isize>,
+    pub require_same_team_sizes: Option<bool>,
+    pub required_team_eliminations: Option<isize>,
+    pub is_scoring_game: Option<bool>,
+    pub score_display_mode: String,
+    pub use_creator_score_target: Option<bool>,
+    pub score_target: Option<isize>,
+    pub score_target_squad_2players: Option<isize>,
+    pub score_target_squad_3players: Option<isize>,
+    pub score_target_squad_4players: Option<isize>,
+    pub is_piggyback_enabled: Option<bool>,
+    pub is_final_round: Option<bool>,
+    pub tom_round_rules: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FGExtraDataLevelsRoundItem {
+    pub id: String,
+    pub display_name: Option<String>,
+    pub game_rules: String,
+    pub round_info: Option<String>,
+    pub main_ambience_state: String,
+    pub loading_screen_name: String,
+    pub level_badge_name: String,
+    pub tags: Vec<String>,
+    pub level_archetype: String,
+    pub fall_feed: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FGExtraDataLocalizedStringsItem {
+    pub id: String,
+    pub text: String,
+}
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FGExtraDataShowsItem {
+    pub id: String,
+    pub show_name: Option<String>,
+    pub show_description: Option<String>,
+    pub content_label: String,
+    pub min_party_size: u32,
+    pub max_party_size: u32,
+    pub show_type: FGExtraDataShowsItemShowType,
+    pub episode_reward_settings_id: String,
+    /// e.g. `levels_episode.episode_ranked_mode`.
+    pub default_episode: Option<String>,
+    /// e.g. `show_tag_creative_mode`, only on some shows.
+    pub show_tag: Option<String>,
+    /// Set on the shows always available, the others count down to their closing.
+    pub hide_countdown: Option<bool>,
+}
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FGExtraDataShowTagsItem {
+    pub id: String,
+    pub category: FGShowCategory,
+}
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FGExtraDataShowsItemShowType {
+    pub showtype_switch: String,
+    // Does not shown when showtype_switch is individual.
+    pub squad_size: Option<u32>,
+}
+
+fn initialize_extra_data_assets() -> FGExtraDataAssets {
+    build_assets(
+        serde_json::from_str(STR_GAME_RULES).unwrap(),
+        serde_json::from_str(STR_LEVELS_ROUND).unwrap(),
+        serde_json::from_str(STR_LOCALIZED_STRINGS).unwrap(),
+        serde_json::from_str(STR_SHOWS).unwrap(),
+        serde_json::from_str(STR_SHOW_TAGS).unwrap(),
+    )
+}
+
+/// Loads the assets, preferring the files of the same name in `override_dir` to the embedded ones.
+///
+/// The strings of other locales than `en` are read from `localised_strings.<locale>.json` in
+/// `override_dir`. Replaces the assets in use, the previous ones are kept on errors.
+pub fn load_extra_data_assets(override_dir: Option<&Path>, locale: &str) -> Result<(), String> {
+    let localized_strings_file = localized_strings_file(locale);
+    if locale != DEFAULT_LOCALE
+        && !override_dir.is_some_and(|dir| dir.join(&localized_strings_file).is_file())
+    {
+        return Err(format!(
+            "locale `{}` needs `{}` in the extra data directory",
+            locale, localized_strings_file
+        ));
+    }
+
+    let assets = build_assets(
+        read_asset(override_dir, "game_rules.json", STR_GAME_RULES)?,
+        read_asset(override_dir, "levels_round.json", STR_LEVELS_ROUND)?,
+        read_asset(override_dir, &localized_strings_file, STR_LOCALIZED_STRINGS)?,
+        read_asset(override_dir, "shows.json", STR_SHOWS)?,
+        read_asset(override_dir, "show_tags.json", STR_SHOW_TAGS)?,
+    );
+    *EXTRA_DATA_ASSETS.write().unwrap() = Arc::new(assets);
+    Ok(())
+}
+
+fn localized_strings_file(locale: &str) -> String {
+    if locale == DEFAULT_LOCALE {
+        "localised_strings.json".to_owned()
+    } else {
+        format!("localised_strings.{}.json", locale)
+    }
+}
+
+/// The files read from the data directory for the locale.
+#[cfg(feature = "watch")]
+fn asset_file_names(locale: &str) -> [String; 5] {
+    [
+        "game_rules.json".to_owned(),
+        "levels_round.json".to_owned(),
+        localized_strings_file(locale),
+        "shows.json".to_owned(),
+        "show_tags.json".to_owned(),
+    ]
+}
+
+fn read_asset<T: DeserializeOwned>(
+    override_dir: Option<&Path>,
+    file_name: &str,
+    embedded: &str,
+) -> Result<Vec<T>, String> {
+    match override_dir.map(|dir| dir.join(file_name)) {
+        Some(path) if path.is_file() => {
+            let text = fs::read_to_string(&path)
+                .map_err(|err| format!("could not read {}: {}", path.display(), err))?;
+            serde_json::from_str(&text)
+                .map_err(|err| format!("invalid {}: {}", path.display(), err))
+        }
+        _ => serde_json::from_str(embedded)
+            .map_err(|err| format!("invalid embedded {}: {}", file_name, err)),
+    }
+}
+
+fn build_assets(
+    game_rules_vec: Vec<FGExtraDataGameRulesItem>,
+    round_item: Vec<FGExtraDataLevelsRoundItem>,
+    localized_strings_list: Vec<FGExtraDataLocalizedStringsItem>,
+    show_item: Vec<FGExtraDataShowsItem>,
+    show_tag_item: Vec<FGExtraDataShowTagsItem>,
+) -> FGExtraDataAssets {
+    let mut game_rules = HashMap::new();
+    for item in game_rules_vec {
+        game_rules.insert(item.id.clone(), item);
+    }
+
+    let mut levels_round = HashMap::new();
+    for item in round_item {
+        levels_round.insert(item.id.clone(), item);
+    }
+
+    let mut localized_strings = HashMap::new();
+    for item in localized_strings_list {
+        localized_strings.insert(item.id, item.text);
+    }
+
+    let mut shows = HashMap::new();
+    for item in show_item {
+        shows.insert(item.id.clone(), item);
+    }
+
+    let mut show_tags = HashMap::new();
+    for item in show_tag_item {
+        show_tags.insert(item.id, item.category);
+    }
+
+    FGExtraDataAssets {
+        game_rules,
+        levels_round,
+        localized_strings,
+        shows,
+        show_tags,
+    }
+}
+
+#[test]
+fn test_init_assets() {
+    initialize_extra_data_assets();
+}
+
+pub fn localized_string(key: &str) -> String {
+    let mut key = key.to_owned();
+    if key.starts_with("localised_strings.") {
+        key = key.replace("localised_strings.", "");
+    }
+
+    let res = extra_data_assets().localized_strings.get(&key).cloned();
+    match res {
+        Some(text) => text,
+        None => format!("unknown_localized_key.{}", key),
+    }
+}
+
+pub fn localized_string_round_id(round_id: &str) -> String {
+    match extra_data_assets().levels_round.get(round_id) {
+        Some(round) => {
+            if let Some(display_name) = &round.display_name {
+                localized_string(display_name)
+            } else {
+                format!("{} (no display name)", round_id)
+            }
+        }
+        None => format!("{} (Unknown)", round_id),
+    }
+}
+
+pub fn localized_string_show_id(show_id: &str) -> String {
+    match extra_data_assets().shows.get(show_id) {
+        Some(show) => {
+            if let Some(show_name) = &show.show_name {
+                localized_string(show_name)
+            } else {
+                format!("{} (no display name)", show_id)
+            }
+        }
+        None => format!("{} (Unknown)", show_id),
+    }
+}
+
+/// Lists the problems of the assets, e.g. the missing references between the files.
+pub fn check_extra_data_assets(assets: &FGExtraDataAssets) -> Vec<String> {
+    let mut problems = Vec::new();
+    for (file, len) in [
+        ("game_rules.json", assets.game_rules.len()),
+        ("levels_round.json", assets.levels_round.len()),
+        ("localised_strings.json", assets.localized_strings.len()),
+        ("shows.json", assets.shows.len()),
+        ("show_tags.json", assets.show_tags.len()),
+    ] {
+        if len == 0 && file == "levels_round.json" {
+            // Not embedded unless in `extra_datas` at build time.
+            problems.push(format!(
+                "{} is empty, the rounds have no profile or qualification slots",
+                file
+            ));
+        } else if len == 0 {
+            problems.push(format!("{} is empty", file));
+        }
+    }
+
+    let has_string = |key: &str| {
+        let key = key.strip_prefix("localised_strings.").unwrap_or(key);
+        assets.localized_strings.contains_key(key)
+    };
+    let mut rounds: Vec<_> = assets.levels_round.values().collect();
+    rounds.sort_by(|a, b| a.id.cmp(&b.id));
+    for round in rounds {
+        let game_rules_id = round
+            .game_rules
+            .strip_prefix("game_rules.")
+            .unwrap_or(&round.game_rules);
+        if !assets.game_rules.contains_key(game_rules_id) {
+            problems.push(format!(
+                "round `{}` refers to unknown game rules `{}`",
+                round.id, round.game_rules
+            ));
+        }
+        if let Some(display_name) = &round.display_name
+            && !has_string(display_name)
+        {
+            problems.push(format!(
+                "round `{}` refers to unknown string `{}`",
+                round.id, display_name
+            ));
+        }
+    }
+    let mut shows: Vec<_> = assets.shows.values().collect();
+    shows.sort_by(|a, b| a.id.cmp(&b.id));
+    for show in shows {
+        if let Some(show_name) = &show.show_name
+            && !has_string(show_name)
+        {
+            problems.push(format!(
+                "show `{}` refers to unknown string `{}`",
+                show.id, show_name
+            ));
+        }
+    }
+    problems
+}
+
+/// Joins the round with its `game_rules`, `levels_round` -> `game_rules`.
+pub fn round_profile(round_id: &str) -> Option<FGRoundProfile> {
+    join_round_profile(&extra_data_assets(), round_id)
+}
+
+fn join_round_profile(assets: &FGExtraDataAssets, round_id: &str) -> Option<FGRoundProfile> {
+    let round = assets.levels_round.get(round_id)?;
+    let game_rules_id = round
+        .game_rules
+        .strip_prefix("game_rules.")
+        .unwrap_or(&round.game_rules);
+    let rules = assets.game_rules.get(game_rules_id)?;
+
+    let archetype = FGRoundArchetype::from(round.level_archetype.as_str());
+    // Some finals are only marked by their archetype.
+    let is_final = rules
+        .is_final_round
+        .unwrap_or(archetype == FGRoundArchetype::Final);
+    Some(FGRoundProfile {
+        archetype,
+        time_limit: (rules.has_timer != Some(false) && rules.duration > 0)
+            .then_some(rules.duration),
+        end_condition: rules.round_end_condition.clone(),
+        qualification_percentage: rules.qualification_percentage,
+        squads_qualification_percentage: rules.squads_qualification_percentage,
+        team_mode: rules.team_mode.clone(),
+        team_count: rules.team_count,
+        is_scoring: rules.is_scoring_game.unwrap_or(false),
+        is_final,
+    })
+}
+
+#[test]
+fn join_round_profiles() {
+    use serde_json::json;
+
+    let rules = |id: &str, has_timer: bool, is_final_round: Option<bool>| {
+        serde_json::from_value::<FGExtraDataGameRulesItem>(json!({
+            "id": id,
+            "qualification_percentage": 70,
+            "squads_qualification_percentage": 50,
+            "has_timer": has_timer,
+            "duration": 180,
+            "time_left_red_alert_threshold": 10,
+            "game_manager_spawn_type": "",
+            "round_end_condition": "success_quota",
+            "overtime_mode": "",
+            "team_mode": "solo",
+            "score_display_mode": "",
+            "is_final_round": is_final_round,
+            "tom_round_rules": "",
+        }))
+        .unwrap()
+    };
+    let round = |id: &str, game_rules: &str, level_archetype: &str| FGExtraDataLevelsRoundItem {
+        id: id.to_owned(),
+        display_name: None,
+        game_rules: game_rules.to_owned(),
+        round_info: None,
+        main_ambience_state: String::new(),
+        loading_screen_name: String::new(),
+        level_badge_name: String::new(),
+        tags: Vec::new(),
+        level_archetype: level_archetype.to_owned(),
+        fall_feed: None,
+    };
+    let assets = build_assets(
+        vec![
+            rules("race", true, None),
+            rules("survival", false, None),
+            rules("final", true, None),
+            rules("marked_final", true, Some(true)),
+        ],
+        vec![
+            round("race", "game_rules.race", "level_archetypes.archetype_race"),
+            round("survival", "game_rules.survival", "archetype_survival"),
+            round(
+                "final",
+                "game_rules.final",
+                "level_archetypes.archetype_final",
+            ),
+            round("marked_final", "marked_final", "new_archetype"),
+            round("missing_rules", "game_rules.missing", "archetype_race"),
+        ],
+        Vec::new(),
+        Vec::new(),
+        Vec::new(),
+    );
+
+    let race = join_round_profile(&assets, "race").unwrap();
+    assert_eq!(race.archetype, FGRoundArchetype::Race);
+    assert_eq!(race.time_limit, Some(180));
+    assert_eq!(race.end_condition, "success_quota");
+    assert_eq!(
+        (
+            race.qualification_percentage,
+            race.squads_qualification_percentage
+        ),
+        (Some(70), Some(50))
+    );
+    assert!(!race.is_final);
+    // The untimed rounds still have a duration in the data.
+    assert_eq!(
+        join_round_profile(&assets, "survival").unwrap().time_limit,
+        None
+    );
+    assert!(join_round_profile(&assets, "final").unwrap().is_final);
+    let marked_final = join_round_profile(&assets, "marked_final").unwrap();
+    assert_eq!(
+        marked_final.archetype,
+        FGRoundArchetype::Unknown("new_archetype".to_owned())
+    );
+    assert!(marked_final.is_final);
+    assert_eq!(join_round_profile(&assets, "missing_rules"), None);
+    assert_eq!(join_round_profile(&assets, "missing_round"), None);
+}
+
+#[test]
+fn get_localized_string_text() {
+    assert_eq!(localized_string("ranked_show_knockout"), "Ranked Knockout");
+    assert_eq!(
+        localized_string("localised_strings.ranked_show_knockout"),
+        "Ranked Knockout"
+    );
+}
@@ -0,0 +1,65 @@
+use std::{path::PathBuf, time::Duration};
+
+use tokio::{
+    sync::mpsc,
+    task::{self, JoinHandle},
+    time::sleep,
+};
+use tracing::{error, info, warn};
+
+use super::{asset_file_names, load_extra_data_assets};
+use crate::parser::task_watch::{FGWatchMode, WatchMessage, watch_dir};
+
+/// The changes within this are reloaded once, the files are usually updated together.
+const RELOAD_DELAY: Duration = Duration::from_millis(500);
+
+/// Reloads the assets from `dir` when its files change, so the new shows and rounds are resolved
+/// without restarting. The previous assets are kept if the files are invalid.
+///
+/// The files are watched as the log file is, with `mode`, waiting for `dir` to appear first.
+/// They are reloaded once at first, for the changes since they have been loaded.
+pub fn reload_on_change(
+    dir: PathBuf,
+    locale: String,
+    mode: FGWatchMode,
+    poll_interval: Duration,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let (changed_tx, mut changed_rx) = mpsc::channel(1024);
+        let dir_path = dir.to_string_lossy().into_owned();
+        for file_name in asset_file_names(&locale) {
+            let mut watch_rx = watch_dir(&dir_path, &file_name, mode, poll_interval).await;
+            let changed_tx = changed_tx.clone();
+            tokio::spawn(async move {
+                while let Some(msg) = watch_rx.recv().await {
+                    // Closed is also reported for reading the files while loading them.
+                    if msg != WatchMessage::Closed && changed_tx.send(()).await.is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(changed_tx);
+
+        while changed_rx.recv().await.is_some() {
+            sleep(RELOAD_DELAY).await;
+            while changed_rx.try_recv().is_ok() {}
+
+            let (dir, locale) = (dir.clone(), locale.clone());
+            let result =
+                task::spawn_blocking(move || load_extra_data_assets(Some(&dir), &locale)).await;
+            match result {
+                Ok(Ok(())) => info!("Reloaded the extra data"),
+                Ok(Err(err)) => warn!(
+                    "Could not reload the extra data, keeping the previous: {}",
+                    err
+                ),
+                // e.g. the loading has panicked, the previous assets are still in place.
+                Err(err) => error!(
+                    "Could not finish reloading the extra data, keeping the previous: {}",
+                    err
+                ),
+            }
+        }
+    })
+}
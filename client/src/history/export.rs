@@ -0,0 +1,83 @@
+use std::io::{self, Write};
+
+use crate::models::history::FGHistoryEpisode;
+
+const CSV_HEADER: &str = "started_at,ended_at,show_id,show_name,session_text,server_ip,kudos,fame,crowns,current_crown_shards,rounds,qualified_rounds,last_round_id";
+
+/// Writes one row per episode, the rounds are summarized into the last columns.
+pub fn write_csv(episodes: &[FGHistoryEpisode], mut writer: impl Write) -> io::Result<()> {
+    writeln!(writer, "{}", CSV_HEADER)?;
+    for episode in episodes {
+        let last_round = episode.rounds.iter().max_by_key(|round| round.round_order);
+        let fields = [
+            episode.started_at.to_string(),
+            optional(episode.ended_at),
+            episode
+                .gamemode
+                .as_ref()
+                .map_or(String::new(), |mode| mode.show_id().to_owned()),
+            optional(episode.gamemode.as_ref()),
+            episode.session_text.clone().unwrap_or_default(),
+            episode.server_ip.clone().unwrap_or_default(),
+            optional(episode.kudos),
+            optional(episode.fame),
+            optional(episode.crowns),
+            optional(episode.current_crown_shards),
+            episode.rounds.len().to_string(),
+            episode
+                .rounds
+                .iter()
+                .filter(|round| round.qualified == Some(true))
+                .count()
+                .to_string(),
+            last_round
+                .map(|round| round.round_id_str.clone())
+                .unwrap_or_default(),
+        ];
+        let row: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        writeln!(writer, "{}", row.join(","))?;
+    }
+    Ok(())
+}
+
+fn optional(value: Option<impl ToString>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_default()
+}
+
+/// Quotes the field if it has a separator, a quote or a line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+#[test]
+fn export_episode_csv() {
+    use crate::models::common::{FGGameMode, FGShowCategory};
+
+    let episode = FGHistoryEpisode {
+        started_at: 1_750_000_000_000,
+        ended_at: None,
+        gamemode: Some(FGGameMode::Extra {
+            name: "Duos, Trios".to_owned(),
+            id: "event_duos".to_owned(),
+            team_size: 2,
+            category: FGShowCategory::LimitedTime,
+        }),
+        session_text: None,
+        server_ip: Some("127.0.0.1".to_owned()),
+        kudos: Some(100),
+        fame: None,
+        crowns: None,
+        current_crown_shards: None,
+        rounds: Vec::new(),
+    };
+    let mut csv = Vec::new();
+    write_csv(&[episode], &mut csv).unwrap();
+    assert_eq!(
+        String::from_utf8(csv).unwrap().lines().nth(1).unwrap(),
+        "1750000000000,,event_duos,\"Duos, Trios (event_duos)\",,127.0.0.1,100,,,,0,0,"
+    );
+}
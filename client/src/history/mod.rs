@@ -0,0 +1,5 @@
+pub mod export;
+pub mod recorder;
+pub mod stats;
+pub mod store;
+pub mod task_history;
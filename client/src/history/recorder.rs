@@ -0,0 +1,230 @@
+use std::collections::HashMap;
+
+use temporal_rs::Instant;
+
+use crate::{
+    lifecycle::episode::FGEpisodeState,
+    models::{
+        history::{FGHistoryEpisode, FGHistoryRound},
+        messages::FGGameMessage,
+        state::FGGameState,
+    },
+};
+
+/// Collects the parsed messages into `FGHistoryEpisode`.
+#[derive(Debug, Clone, Default)]
+pub struct FGEpisodeRecorder {
+    episode: Option<FGHistoryEpisode>,
+    /// Server IP is received before the episode has been joined.
+    server_ip: Option<String>,
+    state: FGEpisodeState,
+}
+
+impl FGEpisodeRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Applies the message, returns the episode when it has been finished.
+    ///
+    /// Episodes without `CompletedEpisodeDto` are returned as well when the next one begins,
+    /// the game returns to the main menu, or the game has been restarted.
+    pub fn apply(
+        &mut self,
+        message: &FGGameMessage,
+        time: Option<&Instant>,
+    ) -> Option<FGHistoryEpisode> {
+        let epoch_millis = time.map(|time| time.epoch_milliseconds());
+        let round_changed = self.state.apply(message);
+
+        match message {
+            FGGameMessage::BeginMatchmaking | FGGameMessage::LogRotated(_) => {
+                self.server_ip = None;
+                self.state.reset();
+                return self.episode.take();
+            }
+            FGGameMessage::GameStateChanged {
+                after: FGGameState::StateMainMenu,
+                ..
+            } => {
+                self.state.reset();
+                return self.episode.take();
+            }
+            FGGameMessage::ServerConnected { ip_addr, .. } => {
+                self.server_ip = Some(ip_addr.clone());
+            }
+            FGGameMessage::SuccessfullyJoined {
+                game_mode,
+                session_text,
+            } => {
+                let finished = self.episode.take();
+                self.state.reset();
+                let Some(started_at) = epoch_millis else {
+                    return finished;
+                };
+                self.episode = Some(FGHistoryEpisode {
+                    started_at,
+                    ended_at: None,
+                    gamemode: Some(game_mode.clone()),
+                    session_text: session_text.clone(),
+                    server_ip: self.server_ip.clone(),
+                    kudos: None,
+                    fame: None,
+                    crowns: None,
+                    current_crown_shards: None,
+                    rounds: Vec::new(),
+                });
+                return finished;
+            }
+            FGGameMessage::LoadedRound(round) => {
+                let Some(episode) = &mut self.episode else {
+                    return None;
+                };
+                episode.rounds.push(FGHistoryRound {
+                    round_order: self.state.round().map_or(0, |round| round.order),
+                    round_id_str: round.id.clone(),
+                    round_display_name: round.display_name.clone(),
+                    started_at: epoch_millis,
+                    ended_at: None,
+                    qualified: None,
+                    platforms: HashMap::new(),
+                    position: None,
+                    team_score: None,
+                    kudos: None,
+                    fame: None,
+                    bonus_tier: None,
+                    bonus_kudos: None,
+                    bonus_fame: None,
+                    badge: None,
+                });
+            }
+            FGGameMessage::SetPlayerProgress { .. } if round_changed => {
+                let qualified = self.state.round().and_then(|round| round.qualified);
+                if let Some(round) = self.last_round() {
+                    round.qualified = qualified;
+                }
+            }
+            FGGameMessage::AppendSpectatorTarget { .. } if round_changed => {
+                let platforms = self.state.round().map(|round| round.platforms.clone());
+                if let (Some(round), Some(platforms)) = (self.last_round(), platforms) {
+                    round.platforms = platforms;
+                }
+            }
+            FGGameMessage::RoundOver => {
+                if let Some(round) = self.last_round() {
+                    round.ended_at = epoch_millis;
+                }
+            }
+            FGGameMessage::GameLobbyRewards(dto) => {
+                let mut episode = self.episode.take()?;
+                episode.ended_at = epoch_millis;
+                episode.kudos = dto.kudos;
+                episode.fame = dto.fame;
+                episode.crowns = dto.crowns;
+                episode.current_crown_shards = dto.current_crown_shards;
+
+                for dto_round in &dto.rounds {
+                    let Some(round) = episode
+                        .rounds
+                        .iter_mut()
+                        .find(|round| round.round_order == dto_round.round_order)
+                    else {
+                        continue;
+                    };
+                    round.qualified = Some(dto_round.qualified);
+                    round.position = Some(dto_round.position);
+                    round.team_score = Some(dto_round.team_score);
+                    round.kudos = Some(dto_round.kudos);
+                    round.fame = Some(dto_round.fame);
+                    round.bonus_tier = Some(dto_round.bonus_tier);
+                    round.bonus_kudos = Some(dto_round.bonus_kudos);
+                    round.bonus_fame = Some(dto_round.bonus_fame);
+                    round.badge = Some(dto_round.badge_id.clone());
+                }
+                return Some(episode);
+            }
+            _ => {}
+        }
+
+        None
+    }
+
+    /// Takes the episode in progress, e.g. when the log has been ended.
+    pub fn finish(&mut self) -> Option<FGHistoryEpisode> {
+        self.episode.take()
+    }
+
+    fn last_round(&mut self) -> Option<&mut FGHistoryRound> {
+        self.episode
+            .as_mut()
+            .and_then(|episode| episode.rounds.last_mut())
+    }
+}
+
+#[test]
+fn record_episode_with_rewards() {
+    use crate::models::{
+        common::{FGGameMode, FGPlatform, FGRoundBadge, FGRoundInfo},
+        dto::{FGCompletedEpisodeDto, generate_fg_completed_episode_dto_round},
+    };
+
+    let time = Instant::from_epoch_milliseconds(1_750_000_000_000).unwrap();
+    let mut recorder = FGEpisodeRecorder::new();
+    let messages = [
+        FGGameMessage::ServerConnected {
+            ip_addr: "127.0.0.1".to_owned(),
+            port: None,
+        },
+        FGGameMessage::SuccessfullyJoined {
+            game_mode: FGGameMode::ClassicSolo,
+            session_text: Some("session".to_owned()),
+        },
+        FGGameMessage::LoadedRound(FGRoundInfo::from_str_id("round_tunnel_40")),
+        FGGameMessage::RequestLocalPlayer(1),
+        FGGameMessage::AppendSpectatorTarget {
+            player_id: 2,
+            squad_id: None,
+            party_id: None,
+            platform: FGPlatform::Switch,
+        },
+        FGGameMessage::AppendSpectatorTarget {
+            player_id: 2,
+            squad_id: None,
+            party_id: None,
+            platform: FGPlatform::Switch,
+        },
+        FGGameMessage::SetPlayerProgress {
+            player_id: 1,
+            is_succeeded: true,
+        },
+        FGGameMessage::RoundOver,
+    ];
+    for message in &messages {
+        assert_eq!(recorder.apply(message, Some(&time)), None);
+    }
+
+    let mut dto_round = generate_fg_completed_episode_dto_round();
+    dto_round.qualified = true;
+    dto_round.position = 3;
+    dto_round.badge_id = FGRoundBadge::Gold;
+    let rewards = FGGameMessage::GameLobbyRewards(FGCompletedEpisodeDto {
+        kudos: Some(100),
+        fame: None,
+        crowns: None,
+        current_crown_shards: Some(10),
+        rounds: vec![dto_round],
+    });
+    let episode = recorder.apply(&rewards, Some(&time)).unwrap();
+
+    assert_eq!(episode.server_ip.as_deref(), Some("127.0.0.1"));
+    assert_eq!(episode.kudos, Some(100));
+    assert_eq!(episode.rounds.len(), 1);
+    assert_eq!(episode.rounds[0].qualified, Some(true));
+    assert_eq!(episode.rounds[0].position, Some(3));
+    assert_eq!(episode.rounds[0].badge, Some(FGRoundBadge::Gold));
+    assert_eq!(
+        episode.rounds[0].platforms,
+        [(FGPlatform::Switch, 1)].into()
+    );
+    assert_eq!(recorder.finish(), None);
+}
@@ -0,0 +1,362 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use serde::Serialize;
+use temporal_rs::{Instant, TimeZone};
+
+use crate::{
+    extra_data::round_profile,
+    models::{
+        common::{FGGameMode, FGPlatform},
+        history::FGHistoryEpisode,
+        lifecycle::FGEpisodeOutcome,
+    },
+};
+
+/// The hours of the day the platform mix is grouped by, in the local time.
+const TIME_OF_DAY_HOURS: u8 = 6;
+
+/// Summary of the recorded episodes, for the `stats` command.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct FGHistoryStats {
+    pub episodes: usize,
+    /// The outcomes, same as the `episode_ended` lifecycle events.
+    pub won: usize,
+    pub eliminated: usize,
+    /// Given the rewards without winning, e.g. left after qualifying from a round.
+    pub left: usize,
+    /// Ended without the rewards.
+    pub abandoned: usize,
+    pub rounds: usize,
+    pub qualified_rounds: usize,
+    pub kudos: isize,
+    pub fame: isize,
+    pub crowns: isize,
+    /// Keyed by the show name.
+    pub shows: BTreeMap<String, FGShowStats>,
+    /// Keyed by the category and the team size of the show, e.g. `limited_time squads`.
+    pub modes: BTreeMap<String, FGShowStats>,
+    /// Keyed by the round archetype, e.g. `race`, or `unknown` if not in the data.
+    pub round_types: BTreeMap<String, FGRoundTypeStats>,
+    /// The lobbies of the first round of the episodes.
+    pub platforms: FGPlatformStats,
+    /// Keyed by the show name.
+    pub platforms_by_show: BTreeMap<String, FGPlatformStats>,
+    /// Keyed by the local hours the episode started in, e.g. `18-24`.
+    pub platforms_by_time: BTreeMap<String, FGPlatformStats>,
+    /// The players per platform code not known yet, so the new ones get noticed.
+    pub unknown_platforms: BTreeMap<String, usize>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct FGShowStats {
+    pub episodes: usize,
+    pub won: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct FGRoundTypeStats {
+    pub rounds: usize,
+    pub qualified: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct FGPlatformStats {
+    /// The episodes with the platforms known.
+    pub lobbies: usize,
+    /// Keyed by the platform code.
+    pub players: BTreeMap<String, usize>,
+}
+
+impl FGPlatformStats {
+    fn total_players(&self) -> usize {
+        self.players.values().sum()
+    }
+}
+
+impl FGHistoryStats {
+    /// The times of the day are in `time_zone`.
+    pub fn from_episodes(episodes: &[FGHistoryEpisode], time_zone: &TimeZone) -> Self {
+        let mut stats = Self::default();
+        for episode in episodes {
+            stats.episodes += 1;
+            stats.rounds += episode.rounds.len();
+            stats.qualified_rounds += episode
+                .rounds
+                .iter()
+                .filter(|round| round.qualified == Some(true))
+                .count();
+            for round in &episode.rounds {
+                let round_type = round_profile(&round.round_id_str)
+                    .map_or("unknown".to_owned(), |profile| {
+                        profile.archetype.to_string()
+                    });
+                let round_stats = stats.round_types.entry(round_type).or_default();
+                round_stats.rounds += 1;
+                if round.qualified == Some(true) {
+                    round_stats.qualified += 1;
+                }
+            }
+            stats.kudos += episode.kudos.unwrap_or(0);
+            stats.fame += episode.fame.unwrap_or(0);
+            stats.crowns += episode.crowns.unwrap_or(0);
+
+            let outcome = episode_outcome(episode);
+            match outcome {
+                FGEpisodeOutcome::Won => stats.won += 1,
+                FGEpisodeOutcome::Eliminated { .. } => stats.eliminated += 1,
+                FGEpisodeOutcome::Left => stats.left += 1,
+                FGEpisodeOutcome::Abandoned => stats.abandoned += 1,
+            }
+            let won = outcome == FGEpisodeOutcome::Won;
+
+            let show = episode
+                .gamemode
+                .as_ref()
+                .map_or("Unknown".to_owned(), |mode| mode.to_string());
+            let mode = episode
+                .gamemode
+                .as_ref()
+                .map_or("unknown".to_owned(), mode_key);
+            // The later rounds only have the players left.
+            let first_round = episode.rounds.iter().min_by_key(|round| round.round_order);
+            if let Some(round) = first_round.filter(|round| !round.platforms.is_empty()) {
+                let time = time_of_day(episode.started_at, time_zone);
+                for platform_stats in [
+                    &mut stats.platforms,
+                    stats.platforms_by_show.entry(show.clone()).or_default(),
+                    stats.platforms_by_time.entry(time).or_default(),
+                ] {
+                    platform_stats.lobbies += 1;
+                    for (platform, players) in &round.platforms {
+                        *platform_stats
+                            .players
+                            .entry(platform.to_string())
+                            .or_default() += usize::try_from(*players).unwrap_or(0);
+                    }
+                }
+                for (platform, players) in &round.platforms {
+                    if let FGPlatform::Unknown(_) = platform {
+                        *stats
+                            .unknown_platforms
+                            .entry(platform.to_string())
+                            .or_default() += usize::try_from(*players).unwrap_or(0);
+                    }
+                }
+            }
+            for show_stats in [
+                stats.shows.entry(show).or_default(),
+                stats.modes.entry(mode).or_default(),
+            ] {
+                show_stats.episodes += 1;
+                if won {
+                    show_stats.won += 1;
+                }
+            }
+        }
+        stats
+    }
+}
+
+fn episode_outcome(episode: &FGHistoryEpisode) -> FGEpisodeOutcome {
+    let has_rewards = episode.kudos.is_some() || episode.crowns.is_some();
+    if !has_rewards {
+        return FGEpisodeOutcome::Abandoned;
+    }
+    let last_round = episode
+        .rounds
+        .iter()
+        .max_by_key(|round| round.round_order)
+        .map(|round| {
+            let is_final =
+                round_profile(&round.round_id_str).is_some_and(|profile| profile.is_final);
+            (round.round_order, round.qualified, is_final)
+        });
+    FGEpisodeOutcome::from_rewards(episode.crowns, last_round)
+}
+
+/// e.g. `18-24` for the episodes started in the evening.
+fn time_of_day(epoch_millis: i64, time_zone: &TimeZone) -> String {
+    let hour = Instant::from_epoch_milliseconds(epoch_millis)
+        .and_then(|instant| {
+            instant
+                .to_zoned_date_time_iso(time_zone.clone())
+                .to_plain_time()
+        })
+        .map_or(0, |time| time.hour());
+    let from = hour - hour % TIME_OF_DAY_HOURS;
+    format!("{:02}-{:02}", from, from + TIME_OF_DAY_HOURS)
+}
+
+fn mode_key(mode: &FGGameMode) -> String {
+    let team = match mode.team_size() {
+        1 => "solo",
+        2 => "duos",
+        3 => "trios",
+        _ => "squads",
+    };
+    format!("{} {}", mode.category(), team)
+}
+
+impl Display for FGHistoryStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Episodes: {} (won {}, eliminated {}, left {}, abandoned {})",
+            self.episodes, self.won, self.eliminated, self.left, self.abandoned
+        )?;
+        let qualified_rate = if self.rounds > 0 {
+            self.qualified_rounds as f64 * 100.0 / self.rounds as f64
+        } else {
+            0.0
+        };
+        writeln!(
+            f,
+            "Rounds: {} (qualified {}, {:.1}%)",
+            self.rounds, self.qualified_rounds, qualified_rate
+        )?;
+        writeln!(
+            f,
+            "Rewards: {} kudos, {} fame, {} crowns",
+            self.kudos, self.fame, self.crowns
+        )?;
+        for (title, groups) in [("Shows", &self.shows), ("Modes", &self.modes)] {
+            writeln!(f, "{}:", title)?;
+            for (group, group_stats) in groups {
+                writeln!(
+                    f,
+                    "  {}: {} episodes, {} won",
+                    group, group_stats.episodes, group_stats.won
+                )?;
+            }
+        }
+        writeln!(f, "Platforms: {}", PlatformShares(&self.platforms))?;
+        for (title, groups) in [
+            ("Platforms by show", &self.platforms_by_show),
+            ("Platforms by time", &self.platforms_by_time),
+        ] {
+            writeln!(f, "{}:", title)?;
+            for (group, platform_stats) in groups {
+                writeln!(f, "  {}: {}", group, PlatformShares(platform_stats))?;
+            }
+        }
+        if !self.unknown_platforms.is_empty() {
+            writeln!(f, "Unknown platforms:")?;
+            for (platform, players) in &self.unknown_platforms {
+                writeln!(f, "  {}: {} players", platform, players)?;
+            }
+        }
+        writeln!(f, "Round types:")?;
+        for (round_type, round_stats) in &self.round_types {
+            writeln!(
+                f,
+                "  {}: {} rounds, {} qualified",
+                round_type, round_stats.rounds, round_stats.qualified
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// e.g. `12 lobbies, ps5 40.0%, switch 60.0%`.
+struct PlatformShares<'a>(&'a FGPlatformStats);
+
+impl Display for PlatformShares<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} lobbies", self.0.lobbies)?;
+        let total = self.0.total_players();
+        for (platform, players) in &self.0.players {
+            let share = *players as f64 * 100.0 / total as f64;
+            write!(f, ", {} {:.1}%", platform, share)?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn summarize_episodes() {
+    use std::collections::HashMap;
+
+    use crate::models::history::FGHistoryRound;
+
+    let round = |round_order, qualified| FGHistoryRound {
+        round_order,
+        round_id_str: "round_tunnel_40".to_owned(),
+        round_display_name: "Roll Out".to_owned(),
+        started_at: None,
+        ended_at: None,
+        qualified,
+        platforms: HashMap::new(),
+        position: None,
+        team_score: None,
+        kudos: None,
+        fame: None,
+        bonus_tier: None,
+        bonus_kudos: None,
+        bonus_fame: None,
+        badge: None,
+    };
+    let episode = |rounds, kudos| FGHistoryEpisode {
+        started_at: 1_750_000_000_000,
+        ended_at: None,
+        gamemode: Some(FGGameMode::ClassicSolo),
+        session_text: None,
+        server_ip: None,
+        kudos,
+        fame: None,
+        crowns: None,
+        current_crown_shards: None,
+        rounds,
+    };
+
+    let mut lobby = round(0, Some(true));
+    lobby.platforms = [
+        (FGPlatform::PlayStation5, 3),
+        (FGPlatform::Unknown(Some("new_console".to_owned())), 1),
+    ]
+    .into();
+    let mut crowned = episode(vec![lobby, round(1, Some(true))], Some(100));
+    crowned.crowns = Some(1);
+    let stats = FGHistoryStats::from_episodes(
+        &[
+            crowned,
+            episode(vec![round(0, Some(true)), round(1, Some(false))], Some(50)),
+            // Qualified from a round which is not the final, then left.
+            episode(vec![round(0, Some(true))], Some(20)),
+            episode(vec![round(0, Some(true))], None),
+        ],
+        &TimeZone::try_from_str("+09:00").unwrap(),
+    );
+    assert_eq!(
+        (stats.won, stats.eliminated, stats.left, stats.abandoned),
+        (1, 1, 1, 1)
+    );
+    assert_eq!((stats.rounds, stats.qualified_rounds), (6, 5));
+    assert_eq!((stats.kudos, stats.crowns), (170, 1));
+    assert_eq!(
+        stats.shows["Classic Solo"],
+        FGShowStats {
+            episodes: 4,
+            won: 1
+        }
+    );
+    assert_eq!(stats.modes["knockout solo"], stats.shows["Classic Solo"]);
+    // The archetype depends on the embedded rounds, which may be missing.
+    let round_types = stats.round_types.values();
+    assert_eq!(
+        round_types.fold((0, 0), |(rounds, qualified), round_stats| (
+            rounds + round_stats.rounds,
+            qualified + round_stats.qualified
+        )),
+        (6, 5)
+    );
+
+    let platforms = FGPlatformStats {
+        lobbies: 1,
+        players: [("new_console".to_owned(), 1), ("ps5".to_owned(), 3)].into(),
+    };
+    assert_eq!(stats.platforms, platforms);
+    assert_eq!(stats.platforms_by_show["Classic Solo"], platforms);
+    // Started at 00:06 in the time zone.
+    assert_eq!(stats.platforms_by_time["00-06"], platforms);
+    assert_eq!(stats.unknown_platforms["new_console"], 1);
+}
@@ -0,0 +1,274 @@
+use std::{collections::HashMap, path::Path};
+
+use rusqlite::{Connection, params};
+
+use crate::models::{
+    common::FGPlatform,
+    history::{FGHistoryEpisode, FGHistoryRound},
+};
+
+const SCHEMA: &str = r#"
+CREATE TABLE IF NOT EXISTS episodes (
+    id INTEGER PRIMARY KEY,
+    started_at INTEGER NOT NULL UNIQUE,
+    ended_at INTEGER,
+    show_id TEXT,
+    show_name TEXT,
+    session_text TEXT,
+    server_ip TEXT,
+    kudos INTEGER,
+    fame INTEGER,
+    crowns INTEGER,
+    current_crown_shards INTEGER
+);
+CREATE TABLE IF NOT EXISTS rounds (
+    episode_id INTEGER NOT NULL REFERENCES episodes(id) ON DELETE CASCADE,
+    round_order INTEGER NOT NULL,
+    round_id TEXT NOT NULL,
+    round_display_name TEXT NOT NULL,
+    started_at INTEGER,
+    ended_at INTEGER,
+    qualified INTEGER,
+    position INTEGER,
+    team_score INTEGER,
+    kudos INTEGER,
+    fame INTEGER,
+    bonus_tier INTEGER,
+    bonus_kudos INTEGER,
+    bonus_fame INTEGER,
+    badge TEXT,
+    PRIMARY KEY (episode_id, round_order)
+);
+CREATE TABLE IF NOT EXISTS round_platforms (
+    episode_id INTEGER NOT NULL REFERENCES episodes(id) ON DELETE CASCADE,
+    round_order INTEGER NOT NULL,
+    -- NULL if the player has no platform in the log.
+    platform TEXT,
+    players INTEGER NOT NULL,
+    PRIMARY KEY (episode_id, round_order, platform)
+);
+"#;
+
+/// The episode history database, stored in SQLite.
+pub struct FGHistoryStore {
+    conn: Connection,
+}
+
+impl FGHistoryStore {
+    pub fn open(path: impl AsRef<Path>) -> rusqlite::Result<Self> {
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    #[cfg(test)]
+    pub fn open_in_memory() -> rusqlite::Result<Self> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(conn: Connection) -> rusqlite::Result<Self> {
+        conn.execute_batch("PRAGMA foreign_keys = ON;")?;
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self { conn })
+    }
+
+    /// Inserts the episode with its rounds.
+    ///
+    /// Episodes are keyed by `started_at`, so replaying the same log again does not duplicate them.
+    /// Returns false if the episode has been already recorded.
+    pub fn insert_episode(&mut self, episode: &FGHistoryEpisode) -> rusqlite::Result<bool> {
+        let tx = self.conn.transaction()?;
+        let inserted = tx.execute(
+            "INSERT INTO episodes (started_at, ended_at, show_id, show_name, session_text, server_ip, kudos, fame, crowns, current_crown_shards)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+             ON CONFLICT (started_at) DO NOTHING",
+            params![
+                episode.started_at,
+                episode.ended_at,
+                episode.gamemode.as_ref().map(|mode| mode.show_id()),
+                episode.gamemode.as_ref().map(|mode| mode.to_string()),
+                episode.session_text,
+                episode.server_ip,
+                episode.kudos,
+                episode.fame,
+                episode.crowns,
+                episode.current_crown_shards,
+            ],
+        )?;
+        if inserted == 0 {
+            return Ok(false);
+        }
+
+        let episode_id = tx.last_insert_rowid();
+        for round in &episode.rounds {
+            tx.execute(
+                "INSERT INTO rounds (episode_id, round_order, round_id, round_display_name, started_at, ended_at, qualified, position, team_score, kudos, fame, bonus_tier, bonus_kudos, bonus_fame, badge)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
+                params![
+                    episode_id,
+                    round.round_order,
+                    round.round_id_str,
+                    round.round_display_name,
+                    round.started_at,
+                    round.ended_at,
+                    round.qualified,
+                    round.position,
+                    round.team_score,
+                    round.kudos,
+                    round.fame,
+                    round.bonus_tier,
+                    round.bonus_kudos,
+                    round.bonus_fame,
+                    round.badge.as_ref().map(|badge| badge.to_string()),
+                ],
+            )?;
+            for (platform, players) in &round.platforms {
+                let platform = match platform {
+                    FGPlatform::Unknown(None) => None,
+                    platform => Some(platform.to_string()),
+                };
+                tx.execute(
+                    "INSERT INTO round_platforms (episode_id, round_order, platform, players)
+                     VALUES (?1, ?2, ?3, ?4)",
+                    params![episode_id, round.round_order, platform, players],
+                )?;
+            }
+        }
+        tx.commit()?;
+
+        Ok(true)
+    }
+
+    /// Lists the episodes started in `[from, to)`, in Unix timestamp milliseconds.
+    pub fn episodes_between(&self, from: i64, to: i64) -> rusqlite::Result<Vec<FGHistoryEpisode>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, started_at, ended_at, show_id, session_text, server_ip, kudos, fame, crowns, current_crown_shards
+             FROM episodes WHERE started_at >= ?1 AND started_at < ?2 ORDER BY started_at",
+        )?;
+        let rows = stmt.query_map(params![from, to], |row| {
+            let show_id: Option<String> = row.get(3)?;
+            Ok((
+                row.get::<_, i64>(0)?,
+                FGHistoryEpisode {
+                    started_at: row.get(1)?,
+                    ended_at: row.get(2)?,
+                    gamemode: show_id.and_then(|id| id.parse().ok()),
+                    session_text: row.get(4)?,
+                    server_ip: row.get(5)?,
+                    kudos: row.get(6)?,
+                    fame: row.get(7)?,
+                    crowns: row.get(8)?,
+                    current_crown_shards: row.get(9)?,
+                    rounds: Vec::new(),
+                },
+            ))
+        })?;
+
+        let mut episodes = Vec::new();
+        for row in rows {
+            let (episode_id, mut episode) = row?;
+            episode.rounds = self.rounds(episode_id)?;
+            episodes.push(episode);
+        }
+        Ok(episodes)
+    }
+
+    fn rounds(&self, episode_id: i64) -> rusqlite::Result<Vec<FGHistoryRound>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT round_order, round_id, round_display_name, started_at, ended_at, qualified, position, team_score, kudos, fame, bonus_tier, bonus_kudos, bonus_fame, badge
+             FROM rounds WHERE episode_id = ?1 ORDER BY round_order",
+        )?;
+        let rows = stmt.query_map(params![episode_id], |row| {
+            let badge: Option<String> = row.get(13)?;
+            Ok(FGHistoryRound {
+                round_order: row.get(0)?,
+                round_id_str: row.get(1)?,
+                round_display_name: row.get(2)?,
+                started_at: row.get(3)?,
+                ended_at: row.get(4)?,
+                qualified: row.get(5)?,
+                platforms: HashMap::new(),
+                position: row.get(6)?,
+                team_score: row.get(7)?,
+                kudos: row.get(8)?,
+                fame: row.get(9)?,
+                bonus_tier: row.get(10)?,
+                bonus_kudos: row.get(11)?,
+                bonus_fame: row.get(12)?,
+                badge: badge.and_then(|badge| badge.parse().ok()),
+            })
+        })?;
+        let mut rounds = rows.collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let mut stmt = self.conn.prepare(
+            "SELECT round_order, platform, players FROM round_platforms WHERE episode_id = ?1",
+        )?;
+        let rows = stmt.query_map(params![episode_id], |row| {
+            Ok((
+                row.get::<_, isize>(0)?,
+                row.get::<_, Option<String>>(1)?,
+                row.get::<_, isize>(2)?,
+            ))
+        })?;
+        for row in rows {
+            let (round_order, platform, players) = row?;
+            if let Some(round) = rounds
+                .iter_mut()
+                .find(|round| round.round_order == round_order)
+            {
+                let platform = platform.map_or(FGPlatform::Unknown(None), |platform| {
+                    FGPlatform::from(platform.as_str())
+                });
+                round.platforms.insert(platform, players);
+            }
+        }
+        Ok(rounds)
+    }
+}
+
+#[test]
+fn store_episode_roundtrip() {
+    use crate::models::common::{FGGameMode, FGRoundBadge};
+
+    let episode = FGHistoryEpisode {
+        started_at: 1_750_000_000_000,
+        ended_at: Some(1_750_000_600_000),
+        gamemode: Some(FGGameMode::ClassicSolo),
+        session_text: Some("session".to_owned()),
+        server_ip: Some("127.0.0.1".to_owned()),
+        kudos: Some(100),
+        fame: Some(20),
+        crowns: None,
+        current_crown_shards: Some(10),
+        rounds: vec![FGHistoryRound {
+            round_order: 0,
+            round_id_str: "round_tunnel_40".to_owned(),
+            round_display_name: "Roll Out".to_owned(),
+            started_at: Some(1_750_000_010_000),
+            ended_at: Some(1_750_000_100_000),
+            qualified: Some(true),
+            platforms: [
+                (FGPlatform::PlayStation5, 3),
+                (FGPlatform::Unknown(Some("new_console".to_owned())), 1),
+                (FGPlatform::Unknown(None), 2),
+            ]
+            .into(),
+            position: Some(3),
+            team_score: Some(0),
+            kudos: Some(50),
+            fame: Some(10),
+            bonus_tier: None,
+            bonus_kudos: None,
+            bonus_fame: None,
+            badge: Some(FGRoundBadge::Gold),
+        }],
+    };
+
+    let mut store = FGHistoryStore::open_in_memory().unwrap();
+    assert!(store.insert_episode(&episode).unwrap());
+    // Replaying the same log should not duplicate it.
+    assert!(!store.insert_episode(&episode).unwrap());
+
+    let episodes = store
+        .episodes_between(1_749_999_999_999, 1_750_000_000_001)
+        .unwrap();
+    assert_eq!(episodes, vec![episode]);
+}
@@ -0,0 +1,55 @@
+use std::{sync::mpsc as std_mpsc, thread::JoinHandle};
+
+use temporal_rs::Instant;
+use tokio::sync::mpsc::{self, Receiver};
+use tracing::{error, info, warn};
+
+use crate::models::{history::FGHistoryEpisode, messages::FGGameMessage};
+
+use super::{recorder::FGEpisodeRecorder, store::FGHistoryStore};
+
+/// Records the finished episodes into the history database.
+///
+/// The messages are passed through as-is for the next consumer.
+/// Join the returned handle before exiting, so the last episode does not get lost.
+pub async fn record_from_rx(
+    mut message_rx: Receiver<(FGGameMessage, Option<Instant>)>,
+    mut store: FGHistoryStore,
+) -> (Receiver<(FGGameMessage, Option<Instant>)>, JoinHandle<()>) {
+    let (tx, rx) = mpsc::channel(1024);
+
+    // SQLite calls are blocking, so keep the connection on its own thread.
+    let (episode_tx, episode_rx) = std_mpsc::channel::<FGHistoryEpisode>();
+    let handle = std::thread::spawn(move || {
+        while let Ok(episode) = episode_rx.recv() {
+            match store.insert_episode(&episode) {
+                Ok(true) => info!("Recorded episode started at {}", episode.started_at),
+                Ok(false) => warn!(
+                    "Episode started at {} is already recorded",
+                    episode.started_at
+                ),
+                Err(err) => error!("Could not record the episode: {}", err),
+            }
+        }
+    });
+
+    tokio::spawn(async move {
+        let mut recorder = FGEpisodeRecorder::new();
+        while let Some((message, time)) = message_rx.recv().await {
+            if let Some(episode) = recorder.apply(&message, time.as_ref()) {
+                let _ = episode_tx.send(episode);
+            }
+
+            if tx.send((message, time)).await.is_err() {
+                break;
+            }
+        }
+
+        // The log has been ended, e.g. replay mode.
+        if let Some(episode) = recorder.finish() {
+            let _ = episode_tx.send(episode);
+        }
+    });
+
+    (rx, handle)
+}
@@ -0,0 +1,28 @@
+//! Parses the Fall Guys log into the typed messages.
+//!
+//! `parser::log_parser::FGLogParser` parses the lines without any runtime. The async pipeline
+//! stages need the `tokio` feature, watching the live log file needs `watch`, and the rest of
+//! the `fg-telemetry-client` binary needs `cli`.
+
+pub mod aggregator;
+pub mod discovery;
+pub mod extra_data;
+pub mod lifecycle;
+pub mod models;
+pub mod parser;
+
+#[cfg(feature = "cli")]
+pub mod cli;
+#[cfg(feature = "cli")]
+pub mod commands;
+#[cfg(feature = "cli")]
+pub mod config;
+#[cfg(feature = "cli")]
+pub mod history;
+#[cfg(feature = "cli")]
+pub mod server;
+#[cfg(feature = "cli")]
+pub mod webhook;
+
+#[cfg(any(test, feature = "test-support"))]
+pub mod test_support;
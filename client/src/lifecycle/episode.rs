@@ -0,0 +1,217 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::models::{
+    common::{FGPlatform, FGPlayerId},
+    dto::FGCompletedEpisodeDto,
+    lifecycle::FGEpisodeOutcome,
+    messages::FGGameMessage,
+};
+
+/// The rounds of the episode in progress, as the local player has played them.
+///
+/// The aggregator, the lifecycle tracker and the history recorder reduce the messages with this,
+/// so they agree on the local player, the qualification and the lobby platforms. Each resets it on its own episode
+/// boundaries.
+#[derive(Debug, Clone, Default)]
+pub struct FGEpisodeState {
+    local_player_id: Option<FGPlayerId>,
+    rounds: Vec<FGRoundState>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FGRoundState {
+    /// Starts from 0, same as `CompletedEpisodeDto`.
+    pub order: isize,
+    /// From the round profile, false if it is missing.
+    pub is_final: bool,
+    /// None if the local player has not been known.
+    pub qualified: Option<bool>,
+    /// The players per platform, from the spectator targets.
+    pub platforms: HashMap<FGPlatform, isize>,
+    /// Players already counted in `platforms`, the targets are appended again on respawns.
+    spectator_targets: HashSet<FGPlayerId>,
+}
+
+impl FGEpisodeState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Applies the message, returns true if the rounds have been changed.
+    pub fn apply(&mut self, message: &FGGameMessage) -> bool {
+        match message {
+            FGGameMessage::LoadedRound(round) => {
+                self.rounds.push(FGRoundState {
+                    order: self.rounds.len() as isize,
+                    is_final: round
+                        .profile
+                        .as_ref()
+                        .is_some_and(|profile| profile.is_final),
+                    ..Default::default()
+                });
+                true
+            }
+            FGGameMessage::RequestLocalPlayer(player_id)
+            | FGGameMessage::HandleLocalPlayer { player_id, .. } => {
+                self.local_player_id = Some(*player_id);
+                false
+            }
+            FGGameMessage::SetPlayerProgress {
+                player_id,
+                is_succeeded,
+            } if self.local_player_id == Some(*player_id) => match self.rounds.last_mut() {
+                Some(round) => round.qualified.replace(*is_succeeded) != Some(*is_succeeded),
+                None => false,
+            },
+            FGGameMessage::AppendSpectatorTarget {
+                player_id,
+                platform,
+                ..
+            } => {
+                let Some(round) = self.rounds.last_mut() else {
+                    return false;
+                };
+                if !round.spectator_targets.insert(*player_id) {
+                    return false;
+                }
+                *round.platforms.entry(platform.clone()).or_default() += 1;
+                true
+            }
+            FGGameMessage::GameLobbyRewards(dto) => {
+                let mut changed = false;
+                for dto_round in &dto.rounds {
+                    if let Some(round) = self
+                        .rounds
+                        .iter_mut()
+                        .find(|round| round.order == dto_round.round_order)
+                    {
+                        changed |= round.qualified.replace(dto_round.qualified)
+                            != Some(dto_round.qualified);
+                    }
+                }
+                changed
+            }
+            _ => false,
+        }
+    }
+
+    /// Forgets the episode, e.g. when the next one has been joined.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// The round being played, or the last one played.
+    pub fn round(&self) -> Option<&FGRoundState> {
+        self.rounds.last()
+    }
+
+    pub fn rounds(&self) -> &[FGRoundState] {
+        &self.rounds
+    }
+
+    /// The outcome of the episode ended with `rewards`, after they have been applied.
+    pub fn outcome(&self, rewards: &FGCompletedEpisodeDto) -> FGEpisodeOutcome {
+        // The rewards may list the rounds not loaded, e.g. the log has been read from the middle.
+        let last_round = self
+            .round()
+            .map(|round| (round.order, round.qualified, round.is_final))
+            .or_else(|| {
+                let round = rewards
+                    .rounds
+                    .iter()
+                    .max_by_key(|round| round.round_order)?;
+                Some((round.round_order, Some(round.qualified), false))
+            });
+        FGEpisodeOutcome::from_rewards(rewards.crowns, last_round)
+    }
+}
+
+#[test]
+fn reduce_local_player_rounds() {
+    use crate::models::{
+        common::{FGRoundArchetype, FGRoundInfo, FGRoundProfile},
+        dto::generate_fg_completed_episode_dto_round,
+    };
+
+    let mut state = FGEpisodeState::new();
+    assert!(
+        state.apply(&FGGameMessage::LoadedRound(FGRoundInfo::from_str_id(
+            "round_tunnel_40"
+        )))
+    );
+    assert!(!state.apply(&FGGameMessage::RequestLocalPlayer(1)));
+    // Other players do not change the qualification.
+    assert!(!state.apply(&FGGameMessage::SetPlayerProgress {
+        player_id: 2,
+        is_succeeded: false,
+    }));
+    assert!(state.apply(&FGGameMessage::SetPlayerProgress {
+        player_id: 1,
+        is_succeeded: true,
+    }));
+    assert_eq!(state.round().unwrap().qualified, Some(true));
+
+    let profile = FGRoundProfile {
+        archetype: FGRoundArchetype::Final,
+        time_limit: Some(300),
+        end_condition: "eliminated_quota".to_owned(),
+        qualification_percentage: None,
+        squads_qualification_percentage: None,
+        team_mode: "solo".to_owned(),
+        team_count: None,
+        is_scoring: false,
+        is_final: true,
+    };
+    state.apply(&FGGameMessage::LoadedRound(FGRoundInfo {
+        id: "round_fall_mountain_hub_complete".to_owned(),
+        display_name: "Fall Mountain".to_owned(),
+        profile: Some(profile),
+    }));
+    let round = state.round().unwrap();
+    assert_eq!(
+        (round.order, round.is_final, round.qualified),
+        (1, true, None)
+    );
+    for (player_id, platform) in [
+        (1, FGPlatform::PCSteam),
+        (2, FGPlatform::Switch),
+        (1, FGPlatform::PCSteam),
+    ] {
+        state.apply(&FGGameMessage::AppendSpectatorTarget {
+            player_id,
+            squad_id: None,
+            party_id: None,
+            platform,
+        });
+    }
+    assert_eq!(
+        state.round().unwrap().platforms,
+        [(FGPlatform::PCSteam, 1), (FGPlatform::Switch, 1)].into()
+    );
+
+    // The rewards fill the rounds the local player has not been known in.
+    let mut dto_round = generate_fg_completed_episode_dto_round();
+    dto_round.round_order = 1;
+    dto_round.qualified = true;
+    let rewards = FGCompletedEpisodeDto {
+        kudos: Some(100),
+        fame: None,
+        crowns: None,
+        current_crown_shards: None,
+        rounds: vec![dto_round],
+    };
+    assert!(state.apply(&FGGameMessage::GameLobbyRewards(rewards.clone())));
+    assert_eq!(state.round().unwrap().qualified, Some(true));
+    assert_eq!(state.outcome(&rewards), FGEpisodeOutcome::Won);
+
+    state.reset();
+    assert!(state.rounds().is_empty());
+    // The local player has to be known again.
+    state.apply(&FGGameMessage::LoadedRound(FGRoundInfo::from_str_id(
+        "round_tunnel_40",
+    )));
+    assert!(!state.apply(&FGGameMessage::SetPlayerProgress {
+        player_id: 1,
+        is_succeeded: true,
+    }));
+}
@@ -0,0 +1,4 @@
+pub mod episode;
+#[cfg(feature = "tokio")]
+pub mod task_lifecycle;
+pub mod tracker;
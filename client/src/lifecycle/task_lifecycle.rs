@@ -0,0 +1,35 @@
+use temporal_rs::Instant;
+use tokio::sync::mpsc::{self, Receiver};
+
+use crate::models::{lifecycle::FGEpisodeEvent, messages::FGGameMessage};
+
+use super::tracker::FGLifecycleTracker;
+
+/// Derives the episode lifecycle events from the parsed messages.
+///
+/// Returns the event receiver, and the messages passed through as-is for the next consumer.
+pub async fn track_from_rx(
+    mut message_rx: Receiver<(FGGameMessage, Option<Instant>)>,
+) -> (
+    Receiver<FGEpisodeEvent>,
+    Receiver<(FGGameMessage, Option<Instant>)>,
+) {
+    let (event_tx, event_rx) = mpsc::channel(1024);
+    let (tx, rx) = mpsc::channel(1024);
+
+    tokio::spawn(async move {
+        let mut tracker = FGLifecycleTracker::new();
+        while let Some((message, time)) = message_rx.recv().await {
+            for event in tracker.apply(&message, time.as_ref()) {
+                // Nobody listening to the events should not stop the messages.
+                let _ = event_tx.send(event).await;
+            }
+
+            if tx.send((message, time)).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    (event_rx, rx)
+}
@@ -0,0 +1,329 @@
+use temporal_rs::Instant;
+
+use super::episode::FGEpisodeState;
+use crate::models::{
+    dto::FGCompletedEpisodeDto,
+    lifecycle::{FGEpisodeEvent, FGEpisodeOutcome},
+    messages::FGGameMessage,
+    state::FGGameState,
+};
+
+#[derive(Debug, Clone, Default)]
+struct FGEpisodeProgress {
+    started_at: Option<i64>,
+    /// The round being played, which has not been ended yet.
+    round: Option<FGRoundProgress>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct FGRoundProgress {
+    order: isize,
+    started_at: Option<i64>,
+}
+
+/// Derives `FGEpisodeEvent`s from the parsed messages.
+#[derive(Debug, Clone, Default)]
+pub struct FGLifecycleTracker {
+    episode: Option<FGEpisodeProgress>,
+    state: FGEpisodeState,
+}
+
+impl FGLifecycleTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Applies the message, returns the events it has caused in order.
+    pub fn apply(
+        &mut self,
+        message: &FGGameMessage,
+        time: Option<&Instant>,
+    ) -> Vec<FGEpisodeEvent> {
+        let epoch_millis = time.map(|time| time.epoch_milliseconds());
+        let mut events = Vec::new();
+
+        match message {
+            FGGameMessage::BeginMatchmaking
+            | FGGameMessage::LogRotated(_)
+            | FGGameMessage::GameStateChanged {
+                after: FGGameState::StateMainMenu,
+                ..
+            } => {
+                self.abandon(epoch_millis, &mut events);
+                self.state.reset();
+            }
+            FGGameMessage::SuccessfullyJoined {
+                game_mode,
+                session_text,
+            } => {
+                self.abandon(epoch_millis, &mut events);
+                self.state.reset();
+                self.episode = Some(FGEpisodeProgress {
+                    started_at: epoch_millis,
+                    ..Default::default()
+                });
+                events.push(FGEpisodeEvent::EpisodeStarted {
+                    show: game_mode.clone(),
+                    session: session_text.clone(),
+                    started_at: epoch_millis,
+                });
+            }
+            FGGameMessage::LoadedRound(round) => {
+                let Some(episode) = &mut self.episode else {
+                    return events;
+                };
+                // The round over message of the previous round has been missed.
+                if let Some(event) = episode.end_round(&self.state, None, epoch_millis) {
+                    events.push(event);
+                }
+                self.state.apply(message);
+                let order = self.state.round().map_or(0, |round| round.order);
+                episode.round = Some(FGRoundProgress {
+                    order,
+                    started_at: epoch_millis,
+                });
+                events.push(FGEpisodeEvent::RoundStarted {
+                    round: round.clone(),
+                    order,
+                    started_at: epoch_millis,
+                });
+            }
+            FGGameMessage::RoundOver => {
+                if let Some(event) = self
+                    .episode
+                    .as_mut()
+                    .and_then(|episode| episode.end_round(&self.state, None, epoch_millis))
+                {
+                    events.push(event);
+                }
+            }
+            FGGameMessage::GameLobbyRewards(dto) => {
+                let Some(mut episode) = self.episode.take() else {
+                    return events;
+                };
+                self.state.apply(message);
+                if let Some(event) = episode.end_round(&self.state, Some(dto), epoch_millis) {
+                    events.push(event);
+                }
+                events.push(FGEpisodeEvent::EpisodeEnded {
+                    outcome: self.state.outcome(dto),
+                    rewards: Some(dto.clone()),
+                    started_at: episode.started_at,
+                    ended_at: epoch_millis,
+                });
+            }
+            _ => {
+                self.state.apply(message);
+            }
+        }
+
+        events
+    }
+
+    /// Ends the episode in progress without the rewards.
+    fn abandon(&mut self, ended_at: Option<i64>, events: &mut Vec<FGEpisodeEvent>) {
+        let Some(mut episode) = self.episode.take() else {
+            return;
+        };
+        if let Some(event) = episode.end_round(&self.state, None, ended_at) {
+            events.push(event);
+        }
+        events.push(FGEpisodeEvent::EpisodeEnded {
+            outcome: FGEpisodeOutcome::Abandoned,
+            rewards: None,
+            started_at: episode.started_at,
+            ended_at,
+        });
+    }
+}
+
+impl FGEpisodeProgress {
+    /// Ends the round being played, with the qualification from the state and the position from
+    /// the rewards if given.
+    fn end_round(
+        &mut self,
+        state: &FGEpisodeState,
+        rewards: Option<&FGCompletedEpisodeDto>,
+        ended_at: Option<i64>,
+    ) -> Option<FGEpisodeEvent> {
+        let round = self.round.take()?;
+        let position = rewards.and_then(|dto| {
+            dto.rounds
+                .iter()
+                .find(|dto_round| dto_round.round_order == round.order)
+                .map(|dto_round| dto_round.position)
+        });
+        let qualified = state
+            .rounds()
+            .iter()
+            .find(|state_round| state_round.order == round.order)
+            .and_then(|state_round| state_round.qualified);
+
+        Some(FGEpisodeEvent::RoundEnded {
+            order: round.order,
+            qualified,
+            position,
+            started_at: round.started_at,
+            ended_at,
+        })
+    }
+}
+
+#[test]
+fn track_episode_lifecycle() {
+    use crate::models::common::{FGGameMode, FGRoundInfo};
+
+    let time = Instant::from_epoch_milliseconds(1_750_000_000_000).unwrap();
+    let mut tracker = FGLifecycleTracker::new();
+    let mut apply = |message| tracker.apply(&message, Some(&time));
+
+    assert_eq!(
+        apply(FGGameMessage::SuccessfullyJoined {
+            game_mode: FGGameMode::ClassicSolo,
+            session_text: None,
+        }),
+        vec![FGEpisodeEvent::EpisodeStarted {
+            show: FGGameMode::ClassicSolo,
+            session: None,
+            started_at: Some(1_750_000_000_000),
+        }]
+    );
+    apply(FGGameMessage::LoadedRound(FGRoundInfo::from_str_id(
+        "round_tunnel_40",
+    )));
+    apply(FGGameMessage::RequestLocalPlayer(1));
+    apply(FGGameMessage::SetPlayerProgress {
+        player_id: 1,
+        is_succeeded: true,
+    });
+    assert_eq!(
+        apply(FGGameMessage::RoundOver),
+        vec![FGEpisodeEvent::RoundEnded {
+            order: 0,
+            qualified: Some(true),
+            position: None,
+            started_at: Some(1_750_000_000_000),
+            ended_at: Some(1_750_000_000_000),
+        }]
+    );
+
+    // A crown wins the episode, even if the rewards do not list the rounds.
+    let rewards = FGCompletedEpisodeDto {
+        kudos: Some(100),
+        fame: None,
+        crowns: Some(1),
+        current_crown_shards: None,
+        rounds: Vec::new(),
+    };
+    assert_eq!(
+        apply(FGGameMessage::GameLobbyRewards(rewards.clone())),
+        vec![FGEpisodeEvent::EpisodeEnded {
+            outcome: FGEpisodeOutcome::Won,
+            rewards: Some(rewards),
+            started_at: Some(1_750_000_000_000),
+            ended_at: Some(1_750_000_000_000),
+        }]
+    );
+    // Returning to the main menu after the rewards does not end it again.
+    assert!(
+        apply(FGGameMessage::GameStateChanged {
+            before: None,
+            after: FGGameState::StateMainMenu,
+        })
+        .is_empty()
+    );
+
+    apply(FGGameMessage::SuccessfullyJoined {
+        game_mode: FGGameMode::ClassicSolo,
+        session_text: None,
+    });
+    apply(FGGameMessage::LoadedRound(FGRoundInfo::from_str_id(
+        "round_tunnel_40",
+    )));
+    let events = apply(FGGameMessage::BeginMatchmaking);
+    assert!(matches!(
+        events.as_slice(),
+        [
+            FGEpisodeEvent::RoundEnded {
+                qualified: None,
+                ..
+            },
+            FGEpisodeEvent::EpisodeEnded {
+                outcome: FGEpisodeOutcome::Abandoned,
+                rewards: None,
+                ..
+            },
+        ]
+    ));
+}
+
+#[test]
+fn track_outcome_from_final_round() {
+    use crate::models::{
+        common::{FGGameMode, FGRoundArchetype, FGRoundInfo, FGRoundProfile},
+        dto::generate_fg_completed_episode_dto_round,
+    };
+
+    let time = Instant::from_epoch_milliseconds(1_750_000_000_000).unwrap();
+    let mut tracker = FGLifecycleTracker::new();
+    let mut play_round = |round: FGRoundInfo| {
+        tracker.apply(
+            &FGGameMessage::SuccessfullyJoined {
+                game_mode: FGGameMode::ClassicSolo,
+                session_text: None,
+            },
+            Some(&time),
+        );
+        tracker.apply(&FGGameMessage::LoadedRound(round), Some(&time));
+        tracker.apply(&FGGameMessage::RequestLocalPlayer(1), Some(&time));
+        tracker.apply(
+            &FGGameMessage::SetPlayerProgress {
+                player_id: 1,
+                is_succeeded: true,
+            },
+            Some(&time),
+        );
+        tracker.apply(&FGGameMessage::RoundOver, Some(&time));
+
+        // Leaving the show still gives the rewards of the rounds played.
+        let mut dto_round = generate_fg_completed_episode_dto_round();
+        dto_round.qualified = true;
+        let rewards = FGCompletedEpisodeDto {
+            kudos: Some(50),
+            fame: None,
+            crowns: None,
+            current_crown_shards: None,
+            rounds: vec![dto_round],
+        };
+        let events = tracker.apply(&FGGameMessage::GameLobbyRewards(rewards), Some(&time));
+        match events.last() {
+            Some(FGEpisodeEvent::EpisodeEnded { outcome, .. }) => outcome.clone(),
+            events => panic!("{:?}", events),
+        }
+    };
+
+    // Qualified a round which is not the final, then left.
+    let round = FGRoundInfo {
+        id: "round_door_dash".to_owned(),
+        display_name: "Door Dash".to_owned(),
+        profile: None,
+    };
+    assert_eq!(play_round(round.clone()), FGEpisodeOutcome::Left);
+
+    let profile = FGRoundProfile {
+        archetype: FGRoundArchetype::Final,
+        time_limit: Some(300),
+        end_condition: "eliminated_quota".to_owned(),
+        qualification_percentage: None,
+        squads_qualification_percentage: None,
+        team_mode: "solo".to_owned(),
+        team_count: None,
+        is_scoring: false,
+        is_final: true,
+    };
+    let final_round = FGRoundInfo {
+        profile: Some(profile),
+        ..round
+    };
+    assert_eq!(play_round(final_round), FGEpisodeOutcome::Won);
+}
@@ -1,30 +1,262 @@
-use std::{backtrace::Backtrace, env, panic};
+use std::{backtrace::Backtrace, env, io, panic, path::PathBuf, process::ExitCode, time::Duration};
 
-use tracing::error;
-
-mod extra_data;
-mod models;
-mod parser;
+use clap::Parser;
+use fg_telemetry_client::{
+    aggregator,
+    cli::{self, FGCommand},
+    commands, config, discovery, extra_data, history, lifecycle, models, parser, server, webhook,
+};
+use tokio::sync::mpsc::Receiver;
+use tracing::{debug, error, info, warn};
 
 #[tokio::main]
-async fn main() {
-    tracing_subscriber::fmt::init();
-    // Workaround for tokio panic handling.
+async fn main() -> ExitCode {
+    // Logs go to stderr, so stdout can be piped from `export`, `stats` and the JSON output.
+    tracing_subscriber::fmt().with_writer(io::stderr).init();
+    // Workaround for tokio panic handling, panics in the spawned tasks are not reported otherwise.
     // https://github.com/tokio-rs/tokio/issues/2002#issuecomment-1020443386
+    // Parser errors are logged and skipped, so this is only for the bugs.
     panic::set_hook(Box::new(|info| {
         error!("Got panic. @info:{}", info);
         error!("Backtrace: {}", Backtrace::force_capture());
-        std::process::abort();
     }));
-    dotenvy::dotenv().unwrap();
-    let log_dir = &env::var("LOG_DIR").unwrap();
-    let log_file = &env::var("LOG_FILE").unwrap();
+    dotenvy::dotenv().ok();
+
+    let cli = cli::FGCli::parse();
+    let command = cli.command.unwrap_or(FGCommand::Watch { format: None });
+    let config_path = cli.config;
+    let (config, reader_rx) = match command {
+        // Listing the rules does not need the configuration.
+        FGCommand::Rules => return commands::print_rules(),
+        FGCommand::Watch { format } => {
+            let Some(mut config) = load_config(config_path) else {
+                return ExitCode::FAILURE;
+            };
+            if let Some(format) = format {
+                config.output.stdout.format = format;
+            }
+            match watch_log_file(&config).await {
+                Some(reader_rx) => (config, reader_rx),
+                None => return ExitCode::FAILURE,
+            }
+        }
+        FGCommand::Replay { files, format } => {
+            let Some(mut config) = load_config(config_path) else {
+                return ExitCode::FAILURE;
+            };
+            if let Some(format) = format {
+                config.output.stdout.format = format;
+            }
+            (config, parser::task_replay::replay_log_files(files).await)
+        }
+        FGCommand::Stats { days, format } => {
+            return load_config(config_path).map_or(ExitCode::FAILURE, |config| {
+                commands::print_stats(&config, days, format)
+            });
+        }
+        FGCommand::Export {
+            days,
+            format,
+            output,
+        } => {
+            return load_config(config_path).map_or(ExitCode::FAILURE, |config| {
+                commands::export(&config, days, format, output)
+            });
+        }
+        FGCommand::CheckAssets => {
+            return load_config(config_path)
+                .map_or(ExitCode::FAILURE, |config| commands::check_assets(&config));
+        }
+        FGCommand::PrintConfig => {
+            return load_config(config_path)
+                .map_or(ExitCode::FAILURE, |config| commands::print_config(&config));
+        }
+    };
+    if let Err(err) =
+        extra_data::load_extra_data_assets(config.extra_data.dir.as_deref(), &config.locale)
+    {
+        error!("Could not load the extra data: {}", err);
+        return ExitCode::FAILURE;
+    }
+    if extra_data::extra_data_assets().levels_round.is_empty() {
+        warn!(
+            "No rounds in the extra data, put levels_round.json in the extra data directory for the round profiles and the qualification slots"
+        );
+    }
+    if let Some(dir) = &config.extra_data.dir {
+        extra_data::task_reload::reload_on_change(
+            dir.clone(),
+            config.locale.clone(),
+            config.log.watch_mode,
+            Duration::from_millis(config.log.poll_interval_ms),
+        );
+    }
+
+    run_pipeline(config, reader_rx).await
+}
+
+fn load_config(path: Option<PathBuf>) -> Option<config::FGConfig> {
+    match config::FGConfig::load(path) {
+        Ok(config) => Some(config),
+        Err(err) => {
+            error!("Invalid configuration: {}", err);
+            None
+        }
+    }
+}
+
+/// Finds the log directory then keeps reading the log file in it.
+async fn watch_log_file(config: &config::FGConfig) -> Option<Receiver<parser::FGLogInput>> {
+    let log_file = &config.log.file;
+    // The configured directories override the discovered one, e.g. for the network shares.
+    let log_dir = match discovery::latest_log_dir(&config.log.dirs, log_file) {
+        Some(log_dir) => log_dir.clone(),
+        None => {
+            let home = env::var("HOME").map(PathBuf::from).unwrap_or_default();
+            let Some(location) = discovery::find_log_dir(&home, log_file) else {
+                error!(
+                    "Could not find the Fall Guys log directory, set `log.dirs` or LOG_DIR to it"
+                );
+                return None;
+            };
+            info!(
+                "Found the log directory from {:?}: {}",
+                location.source,
+                location.dir.display()
+            );
+            location.dir
+        }
+    };
+    let log_dir = &log_dir.to_string_lossy().into_owned();
     let file_path = format!("{}/{}", log_dir, log_file);
 
-    let watch_rx = parser::task_watch::watch_dir(log_dir, log_file).await;
-    let reader_rx = parser::task_watch::read_log_file(watch_rx, &file_path).await;
-    let mut parser_rx = parser::task_parser::parse_from_str_rx(reader_rx).await;
-    while let Some(message) = parser_rx.recv().await {
-        println!("{:?}", message);
+    let watch_rx = parser::task_watch::watch_dir(
+        log_dir,
+        log_file,
+        config.log.watch_mode,
+        Duration::from_millis(config.log.poll_interval_ms),
+    )
+    .await;
+    Some(parser::task_watch::read_log_file(watch_rx, &file_path).await)
+}
+
+/// Parses the log lines then feeds the messages to the configured outputs, until the input ends.
+async fn run_pipeline(
+    config: config::FGConfig,
+    reader_rx: Receiver<parser::FGLogInput>,
+) -> ExitCode {
+    // The log times are in the local time of the game, unless `time_zone` is set.
+    let clock = match config.time_zone().map(parser::log_clock::FGLogClock::today) {
+        Ok(Ok(clock)) => clock,
+        Ok(Err(err)) => {
+            error!("Could not get the current date: {}", err);
+            return ExitCode::FAILURE;
+        }
+        Err(err) => {
+            error!("Invalid configuration: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let parser_rx = parser::task_parser::parse_from_str_rx(reader_rx, clock).await;
+    let (mut current_rx, message_rx) =
+        aggregator::task_aggregator::aggregate_from_rx(parser_rx).await;
+
+    let output = &config.output;
+    let (message_rx, history_handle) = if output.history.enabled {
+        let store = match history::store::FGHistoryStore::open(&output.history.path) {
+            Ok(store) => store,
+            Err(err) => {
+                error!(
+                    "Could not open the history database {}: {}",
+                    output.history.path.display(),
+                    err
+                );
+                return ExitCode::FAILURE;
+            }
+        };
+        let (message_rx, history_handle) =
+            history::task_history::record_from_rx(message_rx, store).await;
+        (message_rx, Some(history_handle))
+    } else {
+        (message_rx, None)
+    };
+    let (lifecycle_rx, message_rx) = lifecycle::task_lifecycle::track_from_rx(message_rx).await;
+    let (lifecycle_rx, webhook_handle) = if !output.webhooks.is_empty() {
+        let (lifecycle_rx, webhook_handle) =
+            webhook::task_webhook::post_from_rx(lifecycle_rx, output.webhooks.clone()).await;
+        (lifecycle_rx, Some(webhook_handle))
+    } else {
+        (lifecycle_rx, None)
+    };
+
+    let mut message_rx = if output.http.enabled {
+        match server::task_server::serve_from_rx(
+            message_rx,
+            current_rx.clone(),
+            lifecycle_rx,
+            &output.http,
+        )
+        .await
+        {
+            Ok(message_rx) => message_rx,
+            Err(err) => {
+                error!(
+                    "Could not bind the HTTP API to {}: {}",
+                    output.http.address, err
+                );
+                return ExitCode::FAILURE;
+            }
+        }
+    } else {
+        message_rx
+    };
+
+    tokio::spawn(async move {
+        while current_rx.changed().await.is_ok() {
+            debug!("Current: {:?}", *current_rx.borrow_and_update());
+        }
+    });
+
+    while let Some((message, time)) = message_rx.recv().await {
+        if !output.stdout.enabled {
+            continue;
+        }
+        match output.stdout.format {
+            config::FGStdoutFormat::Debug => println!("{:?}", (message, time)),
+            config::FGStdoutFormat::Json | config::FGStdoutFormat::Pretty => {
+                let event = models::schema::FGVersioned::new(models::schema::FGTimedMessage {
+                    time: time.as_ref().map(|time| time.epoch_milliseconds()),
+                    message,
+                });
+                let json = if output.stdout.format == config::FGStdoutFormat::Pretty {
+                    serde_json::to_string_pretty(&event)
+                } else {
+                    serde_json::to_string(&event)
+                };
+                match json {
+                    Ok(json) => println!("{}", json),
+                    Err(err) => warn!("Could not serialize the message: {}", err),
+                }
+            }
+        }
+    }
+
+    let mut exit_code = ExitCode::SUCCESS;
+    if let Some(webhook_handle) = webhook_handle
+        && let Err(err) = webhook_handle.await
+    {
+        error!(
+            "The webhooks have stopped before posting every event: {}",
+            err
+        );
+        exit_code = ExitCode::FAILURE;
+    }
+    if let Some(history_handle) = history_handle {
+        let joined = tokio::task::spawn_blocking(move || history_handle.join()).await;
+        if !matches!(joined, Ok(Ok(()))) {
+            error!("The history has stopped before recording every episode");
+            exit_code = ExitCode::FAILURE;
+        }
     }
+    exit_code
 }
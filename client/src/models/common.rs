@@ -1,10 +1,16 @@
-use std::{fmt::Display, str::FromStr};
+use std::{convert::Infallible, fmt::Display, str::FromStr};
 
-use crate::extra_data::{EXTRA_DATA_ASSETS, localized_string_round_id, localized_string_show_id};
+use serde::{Deserialize, Serialize};
+use serde_with::{DeserializeFromStr, SerializeDisplay};
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+use crate::extra_data::{
+    FGExtraDataAssets, FGExtraDataShowsItem, extra_data_assets, localized_string_round_id,
+    localized_string_show_id, round_profile,
+};
 
 /// The Game Mode enum.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
 pub enum FGGameMode {
     Knockout,
     RankedKnockout,
@@ -21,63 +27,189 @@ pub enum FGGameMode {
     Extra {
         name: String,
         id: String,
+        /// The players in a team, 1 for the individual shows.
+        #[serde(default = "default_team_size")]
+        team_size: u32,
+        #[serde(default)]
+        category: FGShowCategory,
     },
-    /// We don't know this gamemode, but try to fallback parse then use it if possible.
-    UnknownAssumed(Box<FGGameMode>, String),
-    /// If UnknownAssumed fails, this will throw.
+    /// Missing in the `shows` data, e.g. a new show before the data is updated.
     Unknown(String),
 }
 
 impl FromStr for FGGameMode {
     type Err = String;
-    /// Parsed from id.
+    /// Parsed from id, resolved from `shows.json`.
     fn from_str(text: &str) -> Result<FGGameMode, String> {
-        let val = match text {
-            "ranked_show_knockout" => FGGameMode::RankedKnockout,
-            "knockout_mode" => FGGameMode::Knockout,
-            "classic_solo_main_show" => FGGameMode::ClassicSolo,
-            "classic_duos_show" => FGGameMode::ClassicDuo,
-            "classic_squads_show" => FGGameMode::ClassicSquads,
-            "spotlight_mode" => FGGameMode::CreatorSpotlight,
-            "casual_show" => FGGameMode::Explore,
-            _ => {
-                // Get extra shows from `shows.json`.
-                let game_id = text.to_owned();
-                if EXTRA_DATA_ASSETS.shows.contains_key(&game_id) {
-                    return Ok(FGGameMode::Extra {
-                        name: localized_string_show_id(&game_id),
-                        id: game_id,
-                    });
-                }
-
-                // Fallback: Assuming.
-                let fallback = if text.contains("solo") {
-                    FGGameMode::ClassicSolo
-                } else if text.contains("duo") {
-                    FGGameMode::ClassicDuo
-                } else if text.contains("squads") {
-                    FGGameMode::ClassicSquads
-                } else if text.contains("ranked") {
-                    FGGameMode::RankedKnockout
-                } else if text.contains("knockout") {
-                    FGGameMode::Knockout
-                } else if text.contains("explore") {
-                    FGGameMode::Explore
-                } else {
-                    FGGameMode::Unknown(text.to_owned())
-                };
-
-                match fallback {
-                    FGGameMode::Unknown(_) => fallback,
-                    _ => FGGameMode::UnknownAssumed(Box::new(fallback), text.to_owned()),
-                }
-            }
+        let assets = extra_data_assets();
+        let Some(show) = assets.shows.get(text) else {
+            return Ok(FGGameMode::Unknown(text.to_owned()));
+        };
+        let val = match NAMED_GAME_MODES.iter().find(|mode| mode.show_id() == text) {
+            Some(mode) => mode.clone(),
+            None => FGGameMode::Extra {
+                name: localized_string_show_id(text),
+                id: text.to_owned(),
+                team_size: team_size_of(show),
+                category: FGShowCategory::from_show(show, &assets),
+            },
         };
 
         Ok(val)
     }
 }
 
+/// The shows with a variant of their own, the others are `FGGameMode::Extra`.
+const NAMED_GAME_MODES: [FGGameMode; 7] = [
+    FGGameMode::Knockout,
+    FGGameMode::RankedKnockout,
+    FGGameMode::ClassicSolo,
+    FGGameMode::ClassicDuo,
+    FGGameMode::ClassicSquads,
+    FGGameMode::Explore,
+    FGGameMode::CreatorSpotlight,
+];
+
+impl FGGameMode {
+    /// The show id, as it appears on `Selected show is ...`.
+    pub fn show_id(&self) -> &str {
+        match self {
+            FGGameMode::Knockout => "knockout_mode",
+            FGGameMode::RankedKnockout => "ranked_show_knockout",
+            FGGameMode::ClassicSolo => "classic_solo_main_show",
+            FGGameMode::ClassicDuo => "classic_duos_show",
+            FGGameMode::ClassicSquads => "classic_squads_show",
+            FGGameMode::Explore => "casual_show",
+            FGGameMode::CreatorSpotlight => "spotlight_mode",
+            FGGameMode::Extra { id, .. } => id,
+            FGGameMode::Unknown(id) => id,
+        }
+    }
+
+    /// The players in a team, 1 for the individual shows and the ones missing in the data.
+    pub fn team_size(&self) -> u32 {
+        match self {
+            FGGameMode::Extra { team_size, .. } => *team_size,
+            FGGameMode::Unknown(_) => 1,
+            _ => extra_data_assets()
+                .shows
+                .get(self.show_id())
+                .map_or(1, team_size_of),
+        }
+    }
+
+    /// `knockout` for the shows missing in the data.
+    pub fn category(&self) -> FGShowCategory {
+        match self {
+            FGGameMode::Extra { category, .. } => *category,
+            FGGameMode::Unknown(_) => FGShowCategory::Knockout,
+            _ => {
+                let assets = extra_data_assets();
+                assets
+                    .shows
+                    .get(self.show_id())
+                    .map_or(FGShowCategory::Knockout, |show| {
+                        FGShowCategory::from_show(show, &assets)
+                    })
+            }
+        }
+    }
+}
+
+/// The squad shows without `squad_size` are as large as their parties.
+fn team_size_of(show: &FGExtraDataShowsItem) -> u32 {
+    match show.show_type.showtype_switch.as_str() {
+        "squad" => show.show_type.squad_size.unwrap_or(show.max_party_size),
+        _ => 1,
+    }
+}
+
+fn default_team_size() -> u32 {
+    1
+}
+
+/// The kind of the show, derived from the `shows` data.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    SerializeDisplay,
+    DeserializeFromStr,
+)]
+pub enum FGShowCategory {
+    #[default]
+    Knockout,
+    Ranked,
+    Explore,
+    /// The levels made by the players.
+    Creative,
+    /// The shows counting down to their closing, e.g. the events.
+    LimitedTime,
+}
+
+impl FGShowCategory {
+    /// Classified by the episode and the rewards, then by the `show_tags` data, then by whether
+    /// the show counts down to its closing.
+    pub fn from_show(show: &FGExtraDataShowsItem, assets: &FGExtraDataAssets) -> Self {
+        let tag_category = show
+            .show_tag
+            .as_deref()
+            .and_then(|tag| assets.show_tags.get(tag));
+        if show
+            .default_episode
+            .as_deref()
+            .is_some_and(|episode| episode.ends_with("_ranked_mode"))
+        {
+            FGShowCategory::Ranked
+        } else if show.episode_reward_settings_id.starts_with("explore_") {
+            FGShowCategory::Explore
+        } else if let Some(category) = tag_category {
+            *category
+        } else if show.content_label.ends_with("show_content_label_round") {
+            // The single round shows of the creative levels.
+            FGShowCategory::Creative
+        } else if show.hide_countdown == Some(true) {
+            FGShowCategory::Knockout
+        } else {
+            FGShowCategory::LimitedTime
+        }
+    }
+}
+
+impl FromStr for FGShowCategory {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "knockout" => Ok(FGShowCategory::Knockout),
+            "ranked" => Ok(FGShowCategory::Ranked),
+            "explore" => Ok(FGShowCategory::Explore),
+            "creative" => Ok(FGShowCategory::Creative),
+            "limited_time" => Ok(FGShowCategory::LimitedTime),
+            _ => Err(format!("unknown show category `{}`", s)),
+        }
+    }
+}
+
+impl Display for FGShowCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let category = match self {
+            FGShowCategory::Knockout => "knockout",
+            FGShowCategory::Ranked => "ranked",
+            FGShowCategory::Explore => "explore",
+            FGShowCategory::Creative => "creative",
+            FGShowCategory::LimitedTime => "limited_time",
+        };
+        write!(f, "{}", category)
+    }
+}
+
 impl Display for FGGameMode {
     fn fmt(self: &FGGameMode, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -88,11 +220,8 @@ impl Display for FGGameMode {
             FGGameMode::ClassicSquads => write!(f, "Classic Squads"),
             FGGameMode::Explore => write!(f, "Explore"),
             FGGameMode::CreatorSpotlight => write!(f, "Creator Spotlight"),
-            FGGameMode::Extra { name, id } => write!(f, "{} ({})", name, id),
-            FGGameMode::UnknownAssumed(mode, value) => {
-                write!(f, "Unknown: {} (Assumed {})", value, mode)
-            }
-            FGGameMode::Unknown(_) => write!(f, "Unknown"),
+            FGGameMode::Extra { name, id, .. } => write!(f, "{} ({})", name, id),
+            FGGameMode::Unknown(id) => write!(f, "Unknown ({})", id),
         }
     }
 }
@@ -101,13 +230,16 @@ impl Display for FGGameMode {
 pub type FGPlayerId = isize;
 /// When loading a player, NetPlayerID is incremented by 1, but PlayerID is not.
 pub type FGNetPlayerId = isize;
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 
 /// The round data.
 pub struct FGRoundInfo {
     pub id: String,
     /// The display name. Should be retrived from the `levels_round` data.
     pub display_name: String,
+    /// Missing for the creative rounds and the ones not in the data.
+    #[serde(default)]
+    pub profile: Option<FGRoundProfile>,
 }
 impl FGRoundInfo {
     /// id: e.g `round_tunnel_40`
@@ -116,17 +248,88 @@ impl FGRoundInfo {
             FGRoundInfo {
                 id: id.to_owned(),
                 display_name: format!("Creative: {id}"),
+                profile: None,
             }
         } else {
             FGRoundInfo {
                 id: id.to_owned(),
                 display_name: localized_string_round_id(id),
+                profile: round_profile(id),
             }
         }
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// The round joined with its `game_rules`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FGRoundProfile {
+    pub archetype: FGRoundArchetype,
+    /// In seconds, missing if the round has no timer.
+    pub time_limit: Option<isize>,
+    /// e.g. `success_quota`, `eliminated_quota` or `time_elapsed`.
+    pub end_condition: String,
+    /// The players qualifying, in percent.
+    pub qualification_percentage: Option<isize>,
+    pub squads_qualification_percentage: Option<isize>,
+    /// e.g. `solo`, `teams` or `vs`.
+    pub team_mode: String,
+    pub team_count: Option<isize>,
+    pub is_scoring: bool,
+    pub is_final: bool,
+}
+
+/// The `level_archetype` of the round.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, SerializeDisplay, DeserializeFromStr)]
+pub enum FGRoundArchetype {
+    Race,
+    Survival,
+    Team,
+    Logic,
+    Hunt,
+    Final,
+    Unknown(String),
+}
+
+impl From<&str> for FGRoundArchetype {
+    /// Parsed from e.g. `level_archetypes.archetype_race` or `race`.
+    fn from(s: &str) -> Self {
+        let name = s.rsplit('.').next().unwrap_or(s);
+        let name = name.strip_prefix("archetype_").unwrap_or(name);
+        match name {
+            "race" => FGRoundArchetype::Race,
+            "survival" => FGRoundArchetype::Survival,
+            "team" => FGRoundArchetype::Team,
+            "logic" => FGRoundArchetype::Logic,
+            "hunt" => FGRoundArchetype::Hunt,
+            "final" => FGRoundArchetype::Final,
+            _ => FGRoundArchetype::Unknown(s.to_owned()),
+        }
+    }
+}
+
+impl FromStr for FGRoundArchetype {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(s.into())
+    }
+}
+
+impl Display for FGRoundArchetype {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FGRoundArchetype::Race => write!(f, "race"),
+            FGRoundArchetype::Survival => write!(f, "survival"),
+            FGRoundArchetype::Team => write!(f, "team"),
+            FGRoundArchetype::Logic => write!(f, "logic"),
+            FGRoundArchetype::Hunt => write!(f, "hunt"),
+            FGRoundArchetype::Final => write!(f, "final"),
+            FGRoundArchetype::Unknown(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, SerializeDisplay, DeserializeFromStr)]
 /// The Fall Guys platform. Can retrived via `settings_matchmaking_multiplay` data.
 pub enum FGPlatform {
     /// pc_egs
@@ -155,29 +358,57 @@ pub enum FGPlatform {
     Unknown(Option<String>),
 }
 
+impl From<&str> for FGPlatform {
+    fn from(s: &str) -> Self {
+        match s {
+            "pc_egs" => FGPlatform::PCEpicGamesStore,
+            "pc_steam" => FGPlatform::PCSteam,
+            "pc_standalone" => FGPlatform::PCStandalone,
+            "switch" => FGPlatform::Switch,
+            "xb1" => FGPlatform::XboxOne,
+            "xsx" => FGPlatform::XboxSeriesX,
+            "ps4" => FGPlatform::PlayStation4,
+            "ps5" => FGPlatform::PlayStation5,
+            "android_standalone" => FGPlatform::AndroidStandalone,
+            "android_ega" => FGPlatform::AndroidEpicGamesAccount,
+            "ios_ega" => FGPlatform::IOSEpicGamesAccount,
+            // Written for `Unknown(None)`.
+            "unknown" => FGPlatform::Unknown(None),
+            _ => FGPlatform::Unknown(Some(s.to_owned())),
+        }
+    }
+}
+
 impl FromStr for FGPlatform {
-    type Err = String;
+    type Err = Infallible;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "pc_egs" => Ok(FGPlatform::PCEpicGamesStore),
-            "pc_steam" => Ok(FGPlatform::PCSteam),
-            "pc_standalone" => Ok(FGPlatform::PCStandalone),
-            "switch" => Ok(FGPlatform::Switch),
-            "xb1" => Ok(FGPlatform::XboxOne),
-            "xsx" => Ok(FGPlatform::XboxSeriesX),
-            "ps4" => Ok(FGPlatform::PlayStation4),
-            "ps5" => Ok(FGPlatform::PlayStation5),
-            "android_standalone" => Ok(FGPlatform::AndroidStandalone),
-            "android_ega" => Ok(FGPlatform::AndroidEpicGamesAccount),
-            "ios_ega" => Ok(FGPlatform::IOSEpicGamesAccount),
-            _ => Ok(FGPlatform::Unknown(Some(s.to_owned()))),
+        Ok(s.into())
+    }
+}
+
+/// Written as the platform code, so it can be used as a JSON map key.
+impl Display for FGPlatform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FGPlatform::PCEpicGamesStore => write!(f, "pc_egs"),
+            FGPlatform::PCSteam => write!(f, "pc_steam"),
+            FGPlatform::PCStandalone => write!(f, "pc_standalone"),
+            FGPlatform::Switch => write!(f, "switch"),
+            FGPlatform::XboxOne => write!(f, "xb1"),
+            FGPlatform::XboxSeriesX => write!(f, "xsx"),
+            FGPlatform::PlayStation4 => write!(f, "ps4"),
+            FGPlatform::PlayStation5 => write!(f, "ps5"),
+            FGPlatform::AndroidStandalone => write!(f, "android_standalone"),
+            FGPlatform::AndroidEpicGamesAccount => write!(f, "android_ega"),
+            FGPlatform::IOSEpicGamesAccount => write!(f, "ios_ega"),
+            FGPlatform::Unknown(value) => write!(f, "{}", value.as_deref().unwrap_or("unknown")),
         }
     }
 }
 
 pub type FGCreativeShareCode = String;
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, SerializeDisplay, DeserializeFromStr)]
 
 pub enum FGRoundBadge {
     Gold,
@@ -198,7 +429,105 @@ impl FromStr for FGRoundBadge {
             "bronze" => Ok(FGRoundBadge::Bronze),
             "none" => Ok(FGRoundBadge::None),
             "fail" => Ok(FGRoundBadge::Fail),
+            // Written for `Unknown(None)`.
+            "unknown" => Ok(FGRoundBadge::Unknown(None)),
             _ => Ok(FGRoundBadge::Unknown(Some(s.to_string()))),
         }
     }
 }
+
+impl Display for FGRoundBadge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FGRoundBadge::Gold => write!(f, "gold"),
+            FGRoundBadge::Silver => write!(f, "silver"),
+            FGRoundBadge::Bronze => write!(f, "bronze"),
+            FGRoundBadge::None => write!(f, "none"),
+            FGRoundBadge::Fail => write!(f, "fail"),
+            FGRoundBadge::Unknown(value) => write!(f, "{}", value.as_deref().unwrap_or("unknown")),
+        }
+    }
+}
+
+#[test]
+fn classify_shows_from_data() {
+    // A trio event, the show id does not tell it.
+    let mode = FGGameMode::from_str("showcase_fp18").unwrap();
+    assert_eq!(
+        (mode.team_size(), mode.category()),
+        (3, FGShowCategory::LimitedTime)
+    );
+    let mode = FGGameMode::from_str("teams_show_ltm").unwrap();
+    assert_eq!(
+        (mode.team_size(), mode.category()),
+        (2, FGShowCategory::LimitedTime)
+    );
+    let mode = FGGameMode::from_str("wle_mrs_ugc_curated").unwrap();
+    assert_eq!(mode.category(), FGShowCategory::Creative);
+    let mode = FGGameMode::from_str("xtreme_explore").unwrap();
+    assert_eq!(mode.category(), FGShowCategory::Explore);
+    // The named shows are classified from the data as well.
+    let mode = FGGameMode::from_str("ranked_show_knockout").unwrap();
+    assert_eq!(mode, FGGameMode::RankedKnockout);
+    assert_eq!(
+        (mode.team_size(), mode.category()),
+        (1, FGShowCategory::Ranked)
+    );
+    let mode = FGGameMode::from_str("classic_squads_show").unwrap();
+    assert_eq!(mode, FGGameMode::ClassicSquads);
+    assert_eq!(
+        (mode.team_size(), mode.category()),
+        (4, FGShowCategory::Knockout)
+    );
+    assert_eq!(FGGameMode::ClassicDuo.team_size(), 2);
+    // The spotlight is given the explore rewards.
+    assert_eq!(
+        FGGameMode::CreatorSpotlight.category(),
+        FGShowCategory::Explore
+    );
+
+    // Missing in the data, the id is not guessed from.
+    let mode = FGGameMode::from_str("new_squads_show").unwrap();
+    assert_eq!(mode, FGGameMode::Unknown("new_squads_show".to_owned()));
+    assert_eq!(mode.team_size(), 1);
+
+    // The embedded `shows.json` entries.
+    let assets = extra_data_assets();
+    for (id, category) in [
+        ("classic_solo_main_show", FGShowCategory::Knockout),
+        ("classic_duos_show", FGShowCategory::Knockout),
+        ("classic_squads_show", FGShowCategory::Knockout),
+        ("knockout_mode", FGShowCategory::Knockout),
+        ("main_show", FGShowCategory::Knockout),
+        ("squads_4player", FGShowCategory::Knockout),
+        ("turbo_show", FGShowCategory::Knockout),
+        ("ftue_uk_show", FGShowCategory::Knockout),
+        ("ranked_show_knockout", FGShowCategory::Ranked),
+        ("explore_points", FGShowCategory::Explore),
+        (
+            "wle_srs_single_winner_template_filler",
+            FGShowCategory::Creative,
+        ),
+        ("greatestsquads_ltm", FGShowCategory::LimitedTime),
+        ("wle_nature_ltm", FGShowCategory::LimitedTime),
+        ("wle_srs_hw_treat_template", FGShowCategory::LimitedTime),
+        ("wle_srs_hw_trick_template", FGShowCategory::LimitedTime),
+        (
+            "invisibeans_pistachio_template",
+            FGShowCategory::LimitedTime,
+        ),
+    ] {
+        assert_eq!(
+            FGShowCategory::from_show(&assets.shows[id], &assets),
+            category,
+            "{}",
+            id
+        );
+    }
+    // Only the shows without a countdown are permanent.
+    for show in assets.shows.values() {
+        if FGShowCategory::from_show(show, &assets) == FGShowCategory::Knockout {
+            assert_eq!(show.hide_countdown, Some(true), "{}", show.id);
+        }
+    }
+}
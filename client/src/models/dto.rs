@@ -1,6 +1,8 @@
+use serde::{Deserialize, Serialize};
+
 use super::common::FGRoundBadge;
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct FGCompletedEpisodeDto {
     pub kudos: Option<isize>,
     pub fame: Option<isize>,
@@ -10,7 +12,7 @@ pub struct FGCompletedEpisodeDto {
     pub rounds: Vec<FGCompletedEpisodeDtoRound>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct FGCompletedEpisodeDtoRound {
     pub round_order: isize,
     pub round_id_str: String,
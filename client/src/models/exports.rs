@@ -1,36 +1,119 @@
 use std::collections::HashMap;
 
-use super::{common::{FGGameMode, FGPlatform, FGRoundBadge}, messages::FGMatchmakingMessage, state::{FGGameSessionState, FGGameState}};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    common::{FGGameMode, FGNetPlayerId, FGPlatform, FGPlayerId, FGRoundBadge, FGRoundProfile},
+    messages::FGMatchmakingMessage,
+    state::{FGGameSessionState, FGGameState},
+};
 
 /// The initial data to send, just in case previous state is not found.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FGExportsCurrent {
     pub state: Option<FGGameState>,
     pub game_session_state: Option<FGGameSessionState>,
     pub gamemode: Option<FGGameMode>,
+    pub matchmaking: FGExportsMatchmaking,
     pub round_info: Option<FGExportsRoundInfo>,
     pub round_result: Option<FGExportsRoundResult>,
     pub lobby_info: Option<FGExportsLobbyInfo>,
+    #[serde(default)]
+    pub qualification: Option<FGExportsQualification>,
+    #[serde(default)]
+    pub roster: Option<FGExportsRoster>,
 }
 
-pub struct FGExportsMatchmaking(Option<FGMatchmakingMessage>);
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct FGExportsMatchmaking(pub Option<FGMatchmakingMessage>);
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FGExportsRoundInfo {
+    /// Starts from 0, same as `[Round 0 | ...]` of `CompletedEpisodeDto`.
     pub round_order: isize,
     pub round_id_str: String,
     pub round_display_name: String,
     pub duration: Option<isize>,
+    pub profile: Option<FGRoundProfile>,
 }
 
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FGExportsRoundResult {
     pub badge: Option<FGRoundBadge>,
     pub qualified: bool,
-    /// Unix timestamp. Not none when rounds ends with
+    /// Unix timestamp in milliseconds. Not none when rounds ends with
     /// RoundOver message.
     pub round_over_time: Option<usize>,
 }
 
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FGExportsLobbyInfo {
     pub platforms: HashMap<FGPlatform, isize>,
     pub latency: Option<isize>,
+    /// Unix timestamp in milliseconds of the `NetworkMetrics` message that latency came from.
     pub latency_from_time: Option<isize>,
-}
\ No newline at end of file
+}
+
+/// The live qualification counter of the current round.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FGExportsQualification {
+    /// The players bootstrapped into the round, including the local one.
+    pub lobby_size: usize,
+    /// Missing if the qualification percentage of the round is unknown.
+    pub slots: Option<usize>,
+    pub qualified: usize,
+    pub eliminated: usize,
+    /// The slots not taken yet.
+    pub remaining: Option<usize>,
+}
+
+/// The players of the current round, sorted by the player ID.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct FGExportsRoster(pub Vec<FGExportsRosterPlayer>);
+
+impl FGExportsRoster {
+    pub fn player(&self, player_id: FGPlayerId) -> Option<&FGExportsRosterPlayer> {
+        self.0
+            .binary_search_by_key(&player_id, |player| player.player_id)
+            .ok()
+            .map(|index| &self.0[index])
+    }
+
+    pub fn net_player(&self, net_player_id: FGNetPlayerId) -> Option<&FGExportsRosterPlayer> {
+        self.0
+            .iter()
+            .find(|player| player.net_player_id == Some(net_player_id))
+    }
+
+    pub fn local_player(&self) -> Option<&FGExportsRosterPlayer> {
+        self.0.iter().find(|player| player.is_local)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FGExportsRosterPlayer {
+    pub player_id: FGPlayerId,
+    /// Missing until the player is bootstrapped.
+    pub net_player_id: Option<FGNetPlayerId>,
+    pub is_local: bool,
+    pub squad_id: Option<isize>,
+    pub party_id: Option<isize>,
+    /// Missing until the player is added as a spectator target.
+    pub platform: Option<FGPlatform>,
+    pub spawn_state: FGRosterSpawnState,
+    pub score: Option<isize>,
+    pub is_succeeded: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FGRosterSpawnState {
+    /// Known, but not spawned yet.
+    #[default]
+    Pending,
+    Spawned,
+    /// Died, left or the round ended.
+    Unspawned,
+}
@@ -0,0 +1,51 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::common::{FGGameMode, FGPlatform, FGRoundBadge};
+
+/// A single episode (show) recorded to the history database.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FGHistoryEpisode {
+    /// Unix timestamp in milliseconds, from the log time.
+    pub started_at: i64,
+    /// Unix timestamp in milliseconds, from the log time.
+    pub ended_at: Option<i64>,
+    pub gamemode: Option<FGGameMode>,
+    pub session_text: Option<String>,
+    pub server_ip: Option<String>,
+    /// Rewards of `CompletedEpisodeDto`. None when the episode has been abandoned.
+    pub kudos: Option<isize>,
+    pub fame: Option<isize>,
+    pub crowns: Option<isize>,
+    pub current_crown_shards: Option<isize>,
+
+    pub rounds: Vec<FGHistoryRound>,
+}
+
+/// A single round played in the episode.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FGHistoryRound {
+    /// Starts from 0, same as `CompletedEpisodeDto`.
+    pub round_order: isize,
+    pub round_id_str: String,
+    pub round_display_name: String,
+    /// Unix timestamp in milliseconds, when the round has been loaded.
+    pub started_at: Option<i64>,
+    /// Unix timestamp in milliseconds, when the server notified the round is over.
+    pub ended_at: Option<i64>,
+    pub qualified: Option<bool>,
+    /// The players per platform, from the spectator targets.
+    #[serde(default)]
+    pub platforms: HashMap<FGPlatform, isize>,
+
+    // Below are filled from `CompletedEpisodeDto`.
+    pub position: Option<isize>,
+    pub team_score: Option<isize>,
+    pub kudos: Option<isize>,
+    pub fame: Option<isize>,
+    pub bonus_tier: Option<isize>,
+    pub bonus_kudos: Option<isize>,
+    pub bonus_fame: Option<isize>,
+    pub badge: Option<FGRoundBadge>,
+}
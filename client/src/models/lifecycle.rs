@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+
+use super::{
+    common::{FGGameMode, FGRoundInfo},
+    dto::FGCompletedEpisodeDto,
+};
+
+/// High-level events of the episode, derived from the parsed messages.
+///
+/// Times are Unix timestamps in milliseconds, from the log time.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum FGEpisodeEvent {
+    EpisodeStarted {
+        show: FGGameMode,
+        session: Option<String>,
+        started_at: Option<i64>,
+    },
+    RoundStarted {
+        round: FGRoundInfo,
+        /// Starts from 0, same as `CompletedEpisodeDto`.
+        order: isize,
+        started_at: Option<i64>,
+    },
+    RoundEnded {
+        order: isize,
+        /// None if the local player has not been known.
+        qualified: Option<bool>,
+        /// Only known when the round ends with the rewards, e.g. leaving the match.
+        position: Option<isize>,
+        started_at: Option<i64>,
+        ended_at: Option<i64>,
+    },
+    EpisodeEnded {
+        outcome: FGEpisodeOutcome,
+        /// None when the episode has been abandoned before the rewards.
+        rewards: Option<FGCompletedEpisodeDto>,
+        started_at: Option<i64>,
+        ended_at: Option<i64>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum FGEpisodeOutcome {
+    /// Qualified from the final round, or given a crown.
+    Won,
+    /// Eliminated in the round of the given order.
+    Eliminated { round_order: isize },
+    /// Given the rewards without winning, e.g. left the show after qualifying from a round.
+    Left,
+    /// Ended without the rewards, e.g. the game has been closed.
+    Abandoned,
+}
+
+impl FGEpisodeOutcome {
+    /// The outcome of the episode ended with the rewards, the lifecycle events and the history
+    /// stats share this.
+    ///
+    /// `last_round` is the order of the last round played, whether it has been qualified and
+    /// whether it is a final round.
+    pub fn from_rewards(
+        crowns: Option<isize>,
+        last_round: Option<(isize, Option<bool>, bool)>,
+    ) -> Self {
+        match last_round {
+            _ if crowns.is_some_and(|crowns| crowns > 0) => FGEpisodeOutcome::Won,
+            Some((round_order, Some(false), _)) => FGEpisodeOutcome::Eliminated { round_order },
+            // Qualifying from the other rounds then leaving is not a win.
+            Some((_, Some(true), true)) => FGEpisodeOutcome::Won,
+            _ => FGEpisodeOutcome::Left,
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use serde::{Deserialize, Serialize};
+
 use crate::models::{common::FGGameMode, state::FGGameState};
 use crate::models::{
     common::{FGCreativeShareCode, FGNetPlayerId, FGPlatform, FGPlayerId, FGRoundInfo},
@@ -5,7 +7,8 @@ use crate::models::{
     state::{FGClientReadinessState, FGGameSessionState},
 };
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
 pub enum FGGameMessage {
     // [GameStateMachine]
     // Replacing FGClient.StateConnectionAuthentication with FGClient.StateGameLoading
@@ -173,9 +176,40 @@ pub enum FGGameMessage {
     ServerMessageReadyRoundResponse,
     ServerMessageRoundResults,
     ServerMessageEndRound,
+
+    /// The log file has been rotated, the game has been restarted.
+    /// Sent by the log reader, not logged by the game.
+    LogRotated(FGLogRotation),
+
+    /// The line(s) matched by a rule could not be parsed, then have been skipped.
+    /// Sent by the parser, not logged by the game.
+    ParseFailed(FGParseDiagnostic),
+}
+
+/// The parse error as sent to the outputs.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FGParseDiagnostic {
+    pub rule: String,
+    /// e.g. ``missing capture `ip` ``.
+    pub cause: String,
+    /// The offending line, or lines if the rule requested more lines.
+    pub lines: String,
+}
+
+/// How the log file has been replaced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FGLogRotation {
+    /// Renamed to `Player-prev.log`, then the new one has been created.
+    Renamed,
+    /// Truncated in place.
+    Truncated,
+    /// Removed, or created again without renaming.
+    Recreated,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
 pub enum FGMatchmakingMessage {
     /// Connecting to matchmaking server.
     Connecting,
@@ -1,5 +1,8 @@
 pub mod common;
 pub mod dto;
 pub mod exports;
+pub mod history;
+pub mod lifecycle;
 pub mod messages;
+pub mod schema;
 pub mod state;
@@ -0,0 +1,211 @@
+//! The JSON wire format shared by every output (HTTP, WebSocket, files).
+//! See `SCHEMA.md` for the description of each type.
+
+use serde::{Deserialize, Deserializer, Serialize, de};
+
+use super::{lifecycle::FGEpisodeEvent, messages::FGGameMessage};
+
+/// Bumped whenever the serialized form of any model changes incompatibly.
+pub const FG_SCHEMA_VERSION: u32 = 1;
+
+/// Top-level document, `schema_version` is written next to the fields of `data`.
+///
+/// Reading a document of another `schema_version` fails, instead of misreading it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FGVersioned<T> {
+    #[serde(deserialize_with = "deserialize_schema_version")]
+    pub schema_version: u32,
+    #[serde(flatten)]
+    pub data: T,
+}
+
+impl<T> FGVersioned<T> {
+    pub fn new(data: T) -> Self {
+        Self {
+            schema_version: FG_SCHEMA_VERSION,
+            data,
+        }
+    }
+}
+
+fn deserialize_schema_version<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u32, D::Error> {
+    let schema_version = u32::deserialize(deserializer)?;
+    if schema_version != FG_SCHEMA_VERSION {
+        return Err(de::Error::custom(format!(
+            "unsupported schema_version {}, expected {}",
+            schema_version, FG_SCHEMA_VERSION
+        )));
+    }
+    Ok(schema_version)
+}
+
+/// The parsed message with its log time.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FGTimedMessage {
+    /// Unix timestamp in milliseconds, from the log time.
+    pub time: Option<i64>,
+    pub message: FGGameMessage,
+}
+
+/// The derived lifecycle event, wrapped to keep its `type` apart from the document.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FGLifecycleEvent {
+    pub event: FGEpisodeEvent,
+}
+
+#[test]
+fn message_json_format() {
+    use super::state::FGGameState;
+
+    let event = FGVersioned::new(FGTimedMessage {
+        time: Some(1_750_000_000_000),
+        message: FGGameMessage::GameStateChanged {
+            before: Some(FGGameState::StateMainMenu),
+            after: FGGameState::Unknown("StateSomethingNew".to_owned()),
+        },
+    });
+    assert_eq!(
+        serde_json::to_string(&event).unwrap(),
+        r#"{"schema_version":1,"time":1750000000000,"message":{"type":"game_state_changed","data":{"before":"StateMainMenu","after":"StateSomethingNew"}}}"#
+    );
+    assert_eq!(
+        serde_json::to_string(&FGGameMessage::RoundOver).unwrap(),
+        r#"{"type":"round_over"}"#
+    );
+}
+
+#[test]
+fn messages_roundtrip() {
+    use super::{
+        common::{
+            FGGameMode, FGPlatform, FGRoundArchetype, FGRoundBadge, FGRoundInfo, FGRoundProfile,
+        },
+        dto::{FGCompletedEpisodeDto, generate_fg_completed_episode_dto_round},
+        messages::{FGMatchmakingMessage, FGParseDiagnostic},
+        state::{FGClientReadinessState, FGGameSessionState},
+    };
+
+    let mut dto_round = generate_fg_completed_episode_dto_round();
+    dto_round.badge_id = FGRoundBadge::Unknown(Some("platinum".to_owned()));
+    let messages = vec![
+        FGGameMessage::BeginMatchmaking,
+        FGGameMessage::MatchmakingMessageReceived(FGMatchmakingMessage::Queued {
+            queued_players: 10,
+        }),
+        FGGameMessage::SuccessfullyJoined {
+            game_mode: FGGameMode::Unknown("new_duo_show".to_owned()),
+            session_text: None,
+        },
+        FGGameMessage::SetClientReadiness(FGClientReadinessState::ReadyToPlay),
+        FGGameMessage::LoadedRound(FGRoundInfo {
+            id: "round_tunnel_40".to_owned(),
+            display_name: "Roll Out".to_owned(),
+            profile: Some(FGRoundProfile {
+                archetype: FGRoundArchetype::Unknown("new_archetype".to_owned()),
+                time_limit: Some(300),
+                end_condition: "success_quota".to_owned(),
+                qualification_percentage: Some(70),
+                squads_qualification_percentage: None,
+                team_mode: "solo".to_owned(),
+                team_count: None,
+                is_scoring: false,
+                is_final: false,
+            }),
+        }),
+        FGGameMessage::AppendSpectatorTarget {
+            player_id: 1,
+            squad_id: None,
+            party_id: Some(2),
+            platform: FGPlatform::Unknown(Some("new_console".to_owned())),
+        },
+        FGGameMessage::GameSessionState {
+            after: FGGameSessionState::Playing,
+            before: None,
+        },
+        FGGameMessage::GameLobbyRewards(FGCompletedEpisodeDto {
+            kudos: Some(1),
+            fame: None,
+            crowns: None,
+            current_crown_shards: None,
+            rounds: vec![dto_round],
+        }),
+        FGGameMessage::ParseFailed(FGParseDiagnostic {
+            rule: "server_connected".to_owned(),
+            cause: "missing capture `ip`".to_owned(),
+            lines: "[StateConnectToGame] InitiateNetworkConnectRequest with server IP: ".to_owned(),
+        }),
+    ];
+
+    for message in messages {
+        let event = FGVersioned::new(FGTimedMessage {
+            time: None,
+            message,
+        });
+        let json = serde_json::to_string(&event).unwrap();
+        assert_eq!(
+            serde_json::from_str::<FGVersioned<FGTimedMessage>>(&json).unwrap(),
+            event
+        );
+    }
+}
+
+#[test]
+fn reject_other_schema_version() {
+    let json = r#"{"schema_version":2,"time":null,"message":{"type":"round_over"}}"#;
+    let err = serde_json::from_str::<FGVersioned<FGTimedMessage>>(json).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("unsupported schema_version 2, expected 1"),
+        "{}",
+        err
+    );
+    let json = r#"{"time":null,"message":{"type":"round_over"}}"#;
+    assert!(serde_json::from_str::<FGVersioned<FGTimedMessage>>(json).is_err());
+}
+
+#[test]
+fn unknown_variants_roundtrip() {
+    use super::common::{FGPlatform, FGRoundBadge};
+
+    for platform in [
+        FGPlatform::Unknown(None),
+        FGPlatform::Unknown(Some("new_console".to_owned())),
+    ] {
+        let json = serde_json::to_string(&platform).unwrap();
+        assert_eq!(serde_json::from_str::<FGPlatform>(&json).unwrap(), platform);
+    }
+    for badge in [
+        FGRoundBadge::Unknown(None),
+        FGRoundBadge::Unknown(Some("platinum".to_owned())),
+    ] {
+        let json = serde_json::to_string(&badge).unwrap();
+        assert_eq!(serde_json::from_str::<FGRoundBadge>(&json).unwrap(), badge);
+    }
+}
+
+#[test]
+fn current_roundtrip() {
+    use super::{
+        common::FGPlatform,
+        exports::{FGExportsCurrent, FGExportsLobbyInfo},
+    };
+
+    let current = FGVersioned::new(FGExportsCurrent {
+        lobby_info: Some(FGExportsLobbyInfo {
+            platforms: [
+                (FGPlatform::PlayStation5, 3),
+                (FGPlatform::Unknown(Some("new_console".to_owned())), 1),
+                (FGPlatform::Unknown(None), 2),
+            ]
+            .into(),
+            latency: Some(40),
+            latency_from_time: None,
+        }),
+        ..Default::default()
+    });
+    let json = serde_json::to_string(&current).unwrap();
+    assert_eq!(
+        serde_json::from_str::<FGVersioned<FGExportsCurrent>>(&json).unwrap(),
+        current
+    );
+}
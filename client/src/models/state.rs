@@ -1,5 +1,8 @@
-use std::str::FromStr;
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+use std::{fmt::Display, str::FromStr};
+
+use serde_with::{DeserializeFromStr, SerializeDisplay};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, SerializeDisplay, DeserializeFromStr)]
 
 pub enum FGGameState {
     StateMainMenu,
@@ -21,6 +24,17 @@ pub enum FGGameState {
     Unknown(String),
 }
 
+/// Written as it appears on the log, so it parses back to the same value.
+impl Display for FGGameState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FGGameState::Unknown(text) => write!(f, "{}", text),
+            // Variant names are same as the log.
+            _ => write!(f, "{:?}", self),
+        }
+    }
+}
+
 impl FromStr for FGGameState {
     type Err = String;
 
@@ -51,7 +65,7 @@ impl FromStr for FGGameState {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, SerializeDisplay, DeserializeFromStr)]
 pub enum FGClientReadinessState {
     ReceivedLevelDetails,
     LevelLoaded,
@@ -60,6 +74,17 @@ pub enum FGClientReadinessState {
     Unknown(String),
 }
 
+/// Written as it appears on the log, so it parses back to the same value.
+impl Display for FGClientReadinessState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FGClientReadinessState::Unknown(text) => write!(f, "{}", text),
+            // Variant names are same as the log.
+            _ => write!(f, "{:?}", self),
+        }
+    }
+}
+
 impl FromStr for FGClientReadinessState {
     type Err = String;
     fn from_str(text: &str) -> Result<FGClientReadinessState, String> {
@@ -74,7 +99,7 @@ impl FromStr for FGClientReadinessState {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, SerializeDisplay, DeserializeFromStr)]
 pub enum FGGameSessionState {
     Precountdown,
     Countdown,
@@ -84,6 +109,17 @@ pub enum FGGameSessionState {
     Unknown(String),
 }
 
+/// Written as it appears on the log, so it parses back to the same value.
+impl Display for FGGameSessionState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FGGameSessionState::Unknown(text) => write!(f, "{}", text),
+            // Variant names are same as the log.
+            _ => write!(f, "{:?}", self),
+        }
+    }
+}
+
 impl FromStr for FGGameSessionState {
     type Err = String;
     fn from_str(text: &str) -> Result<FGGameSessionState, String> {
@@ -0,0 +1,77 @@
+use std::sync::LazyLock;
+
+use aho_corasick::AhoCorasick;
+
+use super::rules::{FGParserRule, rules};
+
+/// The candidates of a line are kept in a bitmask, so they are selected without allocating.
+const MAX_RULES: usize = u128::BITS as usize;
+
+/// Selects the candidate rules of a line by searching the anchors of every rule at once.
+pub struct FGRuleDispatcher {
+    rules: Vec<FGParserRule>,
+    /// The pattern index is the index of the rule.
+    anchors: AhoCorasick,
+}
+
+pub static RULE_DISPATCHER: LazyLock<FGRuleDispatcher> =
+    LazyLock::new(|| FGRuleDispatcher::new(rules()));
+
+impl FGRuleDispatcher {
+    pub fn new(rules: Vec<FGParserRule>) -> Self {
+        assert!(
+            rules.len() <= MAX_RULES,
+            "at most {} rules can be dispatched",
+            MAX_RULES
+        );
+        let anchors = AhoCorasick::new(rules.iter().map(|rule| rule.anchor))
+            .expect("rule anchors should build the automaton");
+        Self { rules, anchors }
+    }
+
+    /// Returns the rules whose anchor is in the line, in the order they are declared.
+    pub fn candidates<'a>(
+        &'a self,
+        line: &str,
+    ) -> impl Iterator<Item = &'a FGParserRule> + use<'a> {
+        let mut matched = self
+            .anchors
+            .find_overlapping_iter(line)
+            .fold(0u128, |matched, anchor| {
+                matched | 1 << anchor.pattern().as_usize()
+            });
+        std::iter::from_fn(move || {
+            if matched == 0 {
+                return None;
+            }
+            let index = matched.trailing_zeros() as usize;
+            matched &= matched - 1;
+            Some(&self.rules[index])
+        })
+    }
+}
+
+#[test]
+fn dispatch_candidates() {
+    let names = |line| {
+        RULE_DISPATCHER
+            .candidates(line)
+            .map(|rule| rule.name)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        names("10:00:00.200: [Matchmaking] Begin matchmaking solo"),
+        vec!["begin_matchmaking"]
+    );
+    assert_eq!(
+        names("10:00:01.000: [CameraDirector] Set Local Squad ID: 1"),
+        vec!["set_local_squad_id"]
+    );
+    assert!(names("10:00:01.000: [UnityEngine] Unrelated line").is_empty());
+    // Each rule is a candidate once, however many times its anchor is found.
+    assert_eq!(
+        names("[CameraDirector] Set Local Squad ID: [CameraDirector] Set Local Squad ID: 1"),
+        vec!["set_local_squad_id"]
+    );
+}
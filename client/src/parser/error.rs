@@ -0,0 +1,64 @@
+use std::fmt::Display;
+
+use crate::models::messages::FGParseDiagnostic;
+
+/// Why the rule could not parse the line(s) it has been matched with.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum FGParseErrorCause {
+    /// The regex did not match, or the capture group is missing.
+    MissingCapture(&'static str),
+    /// The captured value could not be parsed.
+    InvalidValue { field: &'static str, value: String },
+    /// The line looks like the rule, but the format is not known.
+    UnexpectedFormat(String),
+    /// The rule kept requesting more lines over the limit.
+    BufferOverflow { lines: usize },
+}
+
+impl Display for FGParseErrorCause {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FGParseErrorCause::MissingCapture(name) => write!(f, "missing capture `{}`", name),
+            FGParseErrorCause::InvalidValue { field, value } => {
+                write!(f, "invalid value of `{}`: {:?}", field, value)
+            }
+            FGParseErrorCause::UnexpectedFormat(reason) => {
+                write!(f, "unexpected format: {}", reason)
+            }
+            FGParseErrorCause::BufferOverflow { lines } => {
+                write!(f, "buffered {} lines without completing", lines)
+            }
+        }
+    }
+}
+
+/// The error with the rule and the input, so the line can be reported then skipped.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FGParseError {
+    pub rule: &'static str,
+    /// The offending line, or lines if the rule requested more lines.
+    pub lines: String,
+    pub cause: FGParseErrorCause,
+}
+
+impl Display for FGParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Rule `{}` failed with {} on:\n{}",
+            self.rule, self.cause, self.lines
+        )
+    }
+}
+
+impl std::error::Error for FGParseError {}
+
+impl From<&FGParseError> for FGParseDiagnostic {
+    fn from(err: &FGParseError) -> Self {
+        Self {
+            rule: err.rule.to_owned(),
+            cause: err.cause.to_string(),
+            lines: err.lines.clone(),
+        }
+    }
+}
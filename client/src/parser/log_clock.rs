@@ -0,0 +1,174 @@
+use std::{
+    fs::Metadata,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use temporal_rs::{Duration, Instant, PlainDate, PlainTime, Temporal, TemporalResult, TimeZone};
+
+/// The log time going back over this means the day has passed, in milliseconds.
+/// Longer than an hour, so the daylight saving time change is not taken as a new day.
+const DAY_ROLLOVER_THRESHOLD: i64 = 12 * 60 * 60 * 1000;
+
+/// When the log file has been written, from its metadata.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FGLogAnchor {
+    /// The creation time, the first line is at or after it.
+    Started(Instant),
+    /// The modification time on the file systems without the creation time,
+    /// the last line is at or before it.
+    Modified(Instant),
+}
+
+impl FGLogAnchor {
+    pub fn started_at(&self) -> Option<Instant> {
+        match self {
+            FGLogAnchor::Started(started_at) => Some(*started_at),
+            FGLogAnchor::Modified(_) => None,
+        }
+    }
+}
+
+/// Resolves the `HH:MM:SS.mmm` time of the log lines into instants.
+///
+/// The log has no date, so it is anchored to the date the log file has been started,
+/// then moved to the next day whenever the time wraps around midnight.
+#[derive(Debug, Clone)]
+pub struct FGLogClock {
+    time_zone: TimeZone,
+    date: PlainDate,
+    /// Milliseconds of the day of the last resolved time.
+    last_time: Option<i64>,
+    /// Milliseconds of `date` the log has been modified at, until the first time is resolved.
+    modified_time: Option<i64>,
+}
+
+impl FGLogClock {
+    pub fn new(time_zone: TimeZone, date: PlainDate) -> Self {
+        Self {
+            time_zone,
+            date,
+            last_time: None,
+            modified_time: None,
+        }
+    }
+
+    /// Anchors to today in the given time zone, or the system one if None.
+    pub fn today(time_zone: Option<TimeZone>) -> TemporalResult<Self> {
+        let now = Temporal::now().zoned_date_time_iso(time_zone)?;
+        Ok(Self::new(now.timezone().clone(), now.to_plain_date()?))
+    }
+
+    /// Anchors to the file metadata.
+    pub fn anchor(&mut self, anchor: &FGLogAnchor) -> TemporalResult<()> {
+        match anchor {
+            FGLogAnchor::Started(started_at) => {
+                let zoned = started_at.to_zoned_date_time_iso(self.time_zone.clone());
+                self.anchor_local(zoned.to_plain_date()?, zoned.to_plain_time()?);
+            }
+            FGLogAnchor::Modified(modified_at) => {
+                let zoned = modified_at.to_zoned_date_time_iso(self.time_zone.clone());
+                self.date = zoned.to_plain_date()?;
+                self.last_time = None;
+                self.modified_time = Some(millis_of_day(&zoned.to_plain_time()?));
+            }
+        }
+        Ok(())
+    }
+
+    /// Anchors to the local date and time the log has been started at, e.g. from its header.
+    pub fn anchor_local(&mut self, date: PlainDate, time: PlainTime) {
+        self.date = date;
+        self.last_time = Some(millis_of_day(&time));
+        self.modified_time = None;
+    }
+
+    /// Returns the instant of the log time, moving to the next day if it wrapped around.
+    pub fn resolve(&mut self, time: PlainTime) -> TemporalResult<Instant> {
+        let millis = millis_of_day(&time);
+        let one_day = Duration::new(0, 0, 0, 1, 0, 0, 0, 0, 0, 0)?;
+        // The log modified after midnight has been started the day before, assuming it is
+        // shorter than a day.
+        if let Some(modified_time) = self.modified_time.take()
+            && millis > modified_time
+        {
+            self.date = self.date.subtract(&one_day, None)?;
+        }
+        if let Some(last_time) = self.last_time
+            && last_time - millis > DAY_ROLLOVER_THRESHOLD
+        {
+            self.date = self.date.add(&one_day, None)?;
+        }
+        self.last_time = Some(millis);
+
+        let zoned = self
+            .date
+            .to_zoned_date_time(self.time_zone.clone(), Some(time))?;
+        Ok(zoned.to_instant())
+    }
+}
+
+fn millis_of_day(time: &PlainTime) -> i64 {
+    ((time.hour() as i64 * 60 + time.minute() as i64) * 60 + time.second() as i64) * 1000
+        + time.millisecond() as i64
+}
+
+/// When the log file has been started, which is its creation time.
+/// Falls back to the modification time on the file systems without it.
+///
+/// Both are of the copy for the copied logs, so the header of the log is preferred,
+/// see `FGLogParser`.
+pub fn file_anchor(metadata: &Metadata) -> Option<FGLogAnchor> {
+    let instant = |time: SystemTime| {
+        let millis = time.duration_since(UNIX_EPOCH).ok()?.as_millis();
+        Instant::from_epoch_milliseconds(millis as i64).ok()
+    };
+    match metadata.created() {
+        Ok(created) => instant(created).map(FGLogAnchor::Started),
+        Err(_) => instant(metadata.modified().ok()?).map(FGLogAnchor::Modified),
+    }
+}
+
+#[test]
+fn resolve_across_midnight() {
+    let time_zone = TimeZone::try_from_str("+09:00").unwrap();
+    let mut clock = FGLogClock::new(time_zone, PlainDate::try_new_iso(2020, 1, 1).unwrap());
+    // 2025-06-15T23:59:00+09:00
+    clock
+        .anchor(&FGLogAnchor::Started(
+            Instant::from_epoch_milliseconds(1_749_999_540_000).unwrap(),
+        ))
+        .unwrap();
+
+    let resolve = |clock: &mut FGLogClock, hour, minute, second, millisecond| {
+        clock
+            .resolve(PlainTime::new(hour, minute, second, millisecond, 0, 0).unwrap())
+            .unwrap()
+            .epoch_milliseconds()
+    };
+    assert_eq!(resolve(&mut clock, 23, 59, 30, 0), 1_749_999_570_000);
+    assert_eq!(resolve(&mut clock, 0, 0, 10, 0), 1_749_999_610_000);
+    // Slightly out of order lines are not a new day.
+    assert_eq!(resolve(&mut clock, 0, 0, 9, 500), 1_749_999_609_500);
+    assert_eq!(resolve(&mut clock, 23, 0, 0, 0), 1_750_082_400_000);
+}
+
+#[test]
+fn resolve_before_modification() {
+    let time_zone = TimeZone::try_from_str("+09:00").unwrap();
+    let mut clock = FGLogClock::new(time_zone, PlainDate::try_new_iso(2020, 1, 1).unwrap());
+    // Modified at 2025-06-16T00:10:00+09:00, after the session crossing midnight.
+    clock
+        .anchor(&FGLogAnchor::Modified(
+            Instant::from_epoch_milliseconds(1_750_000_200_000).unwrap(),
+        ))
+        .unwrap();
+
+    let mut resolve = |hour, minute| {
+        clock
+            .resolve(PlainTime::new(hour, minute, 0, 0, 0, 0).unwrap())
+            .unwrap()
+            .epoch_milliseconds()
+    };
+    assert_eq!(resolve(23, 50), 1_749_999_000_000);
+    assert_eq!(resolve(0, 5), 1_749_999_900_000);
+}
@@ -0,0 +1,338 @@
+use temporal_rs::{Instant, PlainDate, PlainTime};
+use tracing::warn;
+
+use crate::models::messages::{FGGameMessage, FGLogRotation};
+
+use super::{
+    FGLogInput, ParseResult, create_regex,
+    dispatch::RULE_DISPATCHER,
+    error::{FGParseError, FGParseErrorCause},
+    log_clock::{FGLogAnchor, FGLogClock},
+    rules::FGParserRule,
+};
+
+/// Rules requesting more lines over this are considered broken.
+pub const MAX_BUFFER_LINES: usize = 100;
+
+/// The lines before the first log time searched for the start of the log.
+const MAX_HEADER_LINES: usize = 50;
+
+/// Parses the log lines one by one into the messages, without any runtime.
+///
+/// Keeps the lines of the rule requesting more of them, and the date of the log times.
+#[derive(Debug, Clone)]
+pub struct FGLogParser {
+    clock: FGLogClock,
+    need_more_lines_rule: Option<&'static FGParserRule>,
+    temp_buffer: String,
+    /// The lines read of the header of the file, None after it.
+    header_lines: Option<usize>,
+}
+
+impl FGLogParser {
+    pub fn new(clock: FGLogClock) -> Self {
+        Self {
+            clock,
+            need_more_lines_rule: None,
+            temp_buffer: String::new(),
+            header_lines: Some(0),
+        }
+    }
+
+    /// Parses the line, returns the message with its time if the line(s) have been completed.
+    ///
+    /// The failed line(s) are returned as the error, then skipped.
+    pub fn parse_line(
+        &mut self,
+        line: &str,
+    ) -> Option<Result<(FGGameMessage, Option<Instant>), FGParseError>> {
+        if let Some(header_lines) = self.header_lines {
+            self.read_header(line, header_lines);
+        }
+
+        let (input, requested_rule, dispatched) = match self.need_more_lines_rule.take() {
+            // The rule requested the next line, so only it is evaluated.
+            Some(rule) => {
+                let mut buf = std::mem::take(&mut self.temp_buffer);
+                buf.push('\n');
+                buf.push_str(line);
+
+                let buffer_lines = buf.lines().count();
+                if buffer_lines > MAX_BUFFER_LINES {
+                    return Some(Err(FGParseError {
+                        rule: rule.name,
+                        lines: buf,
+                        cause: FGParseErrorCause::BufferOverflow {
+                            lines: buffer_lines,
+                        },
+                    }));
+                }
+                (buf, Some(rule), None)
+            }
+            None => (
+                line.to_owned(),
+                None,
+                Some(RULE_DISPATCHER.candidates(line)),
+            ),
+        };
+
+        for rule in requested_rule
+            .into_iter()
+            .chain(dispatched.into_iter().flatten())
+        {
+            match (rule.parse)(&input) {
+                ParseResult::Parsed(data) => {
+                    let time = try_parse_log_time(&input, &mut self.clock);
+                    return Some(Ok((data, time)));
+                }
+                ParseResult::NeedMoreLines => {
+                    self.need_more_lines_rule = Some(rule);
+                    self.temp_buffer = input;
+                    return None;
+                }
+                ParseResult::Error(cause) => {
+                    return Some(Err(FGParseError {
+                        rule: rule.name,
+                        lines: input,
+                        cause,
+                    }));
+                }
+                ParseResult::None => {}
+            }
+        }
+        None
+    }
+
+    /// The lines kept for the rule requesting more of them, never over `MAX_BUFFER_LINES`.
+    pub fn buffered_lines(&self) -> usize {
+        match self.need_more_lines_rule {
+            Some(_) => self.temp_buffer.lines().count(),
+            None => 0,
+        }
+    }
+
+    /// The header lines before the first log time may have when the log has been started,
+    /// e.g. `2025-06-15 23:59:00`, which is preferred as the file metadata is changed by copying.
+    fn read_header(&mut self, line: &str, header_lines: usize) {
+        let time_re = create_regex(r"^[0-9]+:[0-9]+:[0-9]+\.[0-9]+:");
+        if header_lines >= MAX_HEADER_LINES || time_re.is_match(line) {
+            self.header_lines = None;
+            return;
+        }
+        self.header_lines = Some(header_lines + 1);
+
+        let Some((date, time)) = parse_header_date_time(line) else {
+            return;
+        };
+        self.clock.anchor_local(date, time);
+        self.header_lines = None;
+    }
+
+    /// Starts the new log file, returns `LogRotated` if it has been rotated.
+    pub fn start_file(
+        &mut self,
+        anchor: Option<FGLogAnchor>,
+        rotation: Option<FGLogRotation>,
+    ) -> Option<(FGGameMessage, Option<Instant>)> {
+        // Lines of the previous file can not be completed anymore.
+        self.need_more_lines_rule = None;
+        self.temp_buffer = String::new();
+        self.header_lines = Some(0);
+        match &anchor {
+            Some(anchor) => {
+                if let Err(err) = self.clock.anchor(anchor) {
+                    warn!("Could not anchor the log clock: {}", err);
+                }
+            }
+            None => warn!("Unknown start time of the log file, keeping the date"),
+        }
+        let started_at = anchor.and_then(|anchor| anchor.started_at());
+        rotation.map(|rotation| (FGGameMessage::LogRotated(rotation), started_at))
+    }
+
+    /// Feeds the input from the log readers.
+    pub fn feed(
+        &mut self,
+        input: FGLogInput,
+    ) -> Option<Result<(FGGameMessage, Option<Instant>), FGParseError>> {
+        match input {
+            FGLogInput::FileStarted { anchor, rotation } => {
+                self.start_file(anchor, rotation).map(Ok)
+            }
+            FGLogInput::Line(line) => self.parse_line(&line),
+        }
+    }
+}
+
+/// Parses the local date and time of e.g. `2025-06-15 23:59:00` or `2025-06-15T23:59:00`.
+fn parse_header_date_time(line: &str) -> Option<(PlainDate, PlainTime)> {
+    let re = create_regex(
+        r"(?<year>[0-9]{4})-(?<month>[0-9]{2})-(?<day>[0-9]{2})[T ](?<hours>[0-9]{2}):(?<minutes>[0-9]{2}):(?<seconds>[0-9]{2})",
+    );
+    let caps = re.captures(line)?;
+    let date = PlainDate::try_new_iso(
+        caps["year"].parse().ok()?,
+        caps["month"].parse().ok()?,
+        caps["day"].parse().ok()?,
+    )
+    .ok()?;
+    let time = PlainTime::new(
+        caps["hours"].parse().ok()?,
+        caps["minutes"].parse().ok()?,
+        caps["seconds"].parse().ok()?,
+        0,
+        0,
+        0,
+    )
+    .ok()?;
+    Some((date, time))
+}
+
+/// Parses the time of the first line having it, the date is resolved by the clock.
+pub fn try_parse_log_time(log: &str, clock: &mut FGLogClock) -> Option<Instant> {
+    let re = create_regex(
+        r"(?<hours>[0-9]+):(?<minutes>[0-9]+):(?<seconds>[0-9]+).(?<milliseconds>[0-9]+):",
+    );
+
+    for line in log.lines() {
+        let Some(caps) = re.captures(line) else {
+            continue;
+        };
+        // 09:14:00.422
+        let (Ok(hours), Ok(minutes), Ok(seconds), Ok(milliseconds)) = (
+            caps["hours"].parse::<u8>(),
+            caps["minutes"].parse::<u8>(),
+            caps["seconds"].parse::<u8>(),
+            caps["milliseconds"].parse::<u16>(),
+        ) else {
+            continue;
+        };
+
+        let Ok(time) = PlainTime::new(hours, minutes, seconds, milliseconds, 0, 0) else {
+            warn!(
+                "Could not create PlainTime which is {}:{}:{}:{}",
+                hours, minutes, seconds, milliseconds
+            );
+            continue;
+        };
+        match clock.resolve(time) {
+            Ok(instant) => return Some(instant),
+            Err(err) => {
+                warn!(
+                    "Could not resolve the log time {}:{}:{}:{}: {}",
+                    hours, minutes, seconds, milliseconds, err
+                );
+                continue;
+            }
+        }
+    }
+
+    None
+}
+
+#[test]
+fn parse_lines_without_runtime() {
+    use crate::test_support::test_clock;
+
+    let mut parser = FGLogParser::new(test_clock());
+
+    assert!(parser.parse_line("12:00:00.000: unrelated line").is_none());
+    let (message, time) = parser
+        .parse_line("12:00:00.100: [Matchmaking] Begin matchmaking solo")
+        .unwrap()
+        .unwrap();
+    assert_eq!(message, FGGameMessage::BeginMatchmaking);
+    assert_eq!(
+        time.unwrap().epoch_milliseconds(),
+        1_749_956_400_100 // 2025-06-15T12:00:00.100+09:00
+    );
+}
+
+#[test]
+fn anchor_to_log_header() {
+    use crate::test_support::test_clock;
+
+    let mut parser = FGLogParser::new(test_clock());
+    // Copied a week later.
+    let copied_at = Instant::from_epoch_milliseconds(1_750_600_000_000).unwrap();
+    parser.start_file(Some(FGLogAnchor::Started(copied_at)), None);
+
+    assert!(
+        parser
+            .parse_line("Mono path[0] = 'FallGuys_client_Data/Managed'")
+            .is_none()
+    );
+    assert!(
+        parser
+            .parse_line("Log started at 2025-06-15 23:59:00")
+            .is_none()
+    );
+    let (_, time) = parser
+        .parse_line("23:59:30.000: [Matchmaking] Begin matchmaking solo")
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        time.unwrap().epoch_milliseconds(),
+        1_749_999_570_000 // 2025-06-15T23:59:30+09:00
+    );
+    let (_, time) = parser
+        .parse_line("00:00:10.000: [Matchmaking] Begin matchmaking solo")
+        .unwrap()
+        .unwrap();
+    assert_eq!(time.unwrap().epoch_milliseconds(), 1_749_999_610_000);
+
+    // The dates in the lines after the header are not the start of the log.
+    parser.start_file(Some(FGLogAnchor::Started(copied_at)), None);
+    parser.parse_line("10:00:00.000: unrelated line");
+    parser.parse_line("2025-06-15 23:59:00");
+    let (_, time) = parser
+        .parse_line("10:00:01.000: [Matchmaking] Begin matchmaking solo")
+        .unwrap()
+        .unwrap();
+    assert!(time.unwrap().epoch_milliseconds() > copied_at.epoch_milliseconds());
+}
+
+#[test]
+fn report_parse_errors() {
+    use crate::test_support::test_clock;
+
+    let mut parser = FGLogParser::new(test_clock());
+
+    let line = "10:00:00.000: [StateConnectToGame] InitiateNetworkConnectRequest with server IP: ?";
+    assert_eq!(
+        parser.parse_line(line),
+        Some(Err(FGParseError {
+            rule: "server_connected",
+            lines: line.to_owned(),
+            cause: FGParseErrorCause::MissingCapture("ip"),
+        }))
+    );
+
+    // The status message never gets its state.
+    assert!(
+        parser
+            .parse_line("10:00:01.000: [FNMMSClientRemoteService] Status message received: {")
+            .is_none()
+    );
+    for _ in 1..MAX_BUFFER_LINES {
+        assert!(parser.parse_line("  \"queuedPlayers\": 10,").is_none());
+    }
+    assert_eq!(parser.buffered_lines(), MAX_BUFFER_LINES);
+    let err = parser
+        .parse_line("  \"queuedPlayers\": 10,")
+        .unwrap()
+        .unwrap_err();
+    assert_eq!(err.rule, "matchmaking_message_received");
+    assert_eq!(
+        err.cause,
+        FGParseErrorCause::BufferOverflow {
+            lines: MAX_BUFFER_LINES + 1
+        }
+    );
+    // Then skipped, the next line is dispatched again.
+    assert_eq!(parser.buffered_lines(), 0);
+    assert!(matches!(
+        parser.parse_line("10:00:02.000: [Matchmaking] Begin matchmaking solo"),
+        Some(Ok((FGGameMessage::BeginMatchmaking, _)))
+    ));
+}
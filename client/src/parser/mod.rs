@@ -1,14 +1,41 @@
 use cached::proc_macro::cached;
 use regex::Regex;
 
+use crate::models::messages::FGLogRotation;
+
+use error::FGParseErrorCause;
+use log_clock::FGLogAnchor;
+
+pub mod dispatch;
+pub mod error;
+pub mod log_clock;
+pub mod log_parser;
 pub mod rules;
+#[cfg(feature = "tokio")]
 pub mod task_parser;
+#[cfg(feature = "tokio")]
+pub mod task_replay;
+#[cfg(feature = "watch")]
 pub mod task_watch;
+
+/// The input of the parser, sent from the log readers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FGLogInput {
+    /// The log file is read from the start, see `log_clock::file_anchor`.
+    /// `rotation` is None for the first file.
+    FileStarted {
+        anchor: Option<FGLogAnchor>,
+        rotation: Option<FGLogRotation>,
+    },
+    Line(String),
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ParseResult<T> {
     Parsed(T),
     NeedMoreLines,
-    Unreachable,
+    /// The line belongs to the rule, but could not be parsed.
+    Error(FGParseErrorCause),
     None,
 }
 
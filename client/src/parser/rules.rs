@@ -1,10 +1,13 @@
 use std::{str::FromStr, sync::Arc};
 
-use super::create_regex;
+use regex::Captures;
+use tracing::{debug, warn};
+
+use super::{create_regex, error::FGParseErrorCause};
 use crate::{
     extra_data::localized_string_round_id,
     models::{
-        common::{FGPlatform, FGRoundInfo},
+        common::FGRoundInfo,
         dto::{
             FGCompletedEpisodeDto, FGCompletedEpisodeDtoRound,
             generate_fg_completed_episode_dto_round,
@@ -17,6 +20,45 @@ use crate::{
     parser::ParseResult,
 };
 
+/// Returns `ParseResult::Error` from the rule if the expression is `Err`.
+macro_rules! try_parse {
+    ($expr:expr) => {
+        match $expr {
+            Ok(value) => value,
+            Err(cause) => return ParseResult::Error(cause),
+        }
+    };
+}
+
+fn capture<'h>(caps: &Captures<'h>, name: &'static str) -> Result<&'h str, FGParseErrorCause> {
+    caps.name(name)
+        .map(|matched| matched.as_str())
+        .ok_or(FGParseErrorCause::MissingCapture(name))
+}
+
+fn parse_value<T: FromStr>(field: &'static str, value: &str) -> Result<T, FGParseErrorCause> {
+    value.parse().map_err(|_| FGParseErrorCause::InvalidValue {
+        field,
+        value: value.to_owned(),
+    })
+}
+
+fn parse_capture<T: FromStr>(caps: &Captures, name: &'static str) -> Result<T, FGParseErrorCause> {
+    parse_value(name, capture(caps, name)?)
+}
+
+/// Parses the optional capture, missing or empty one is `None`.
+fn parse_optional_capture<T: FromStr>(
+    caps: &Captures,
+    name: &'static str,
+) -> Result<Option<T>, FGParseErrorCause> {
+    caps.name(name)
+        .map(|matched| matched.as_str().replace(" ", ""))
+        .filter(|value| !value.is_empty())
+        .map(|value| parse_value(name, &value))
+        .transpose()
+}
+
 fn game_state_changed(input: &str) -> ParseResult<FGGameMessage> {
     if !(input.contains("[GameStateMachine] Replacing ") && input.contains(" with ")) {
         return ParseResult::None;
@@ -28,8 +70,10 @@ fn game_state_changed(input: &str) -> ParseResult<FGGameMessage> {
         return ParseResult::None;
     };
 
-    let before = caps.name("before").unwrap().as_str().parse().ok();
-    let after = caps.name("after").unwrap().as_str().parse().unwrap();
+    let before = caps
+        .name("before")
+        .and_then(|before| before.as_str().parse().ok());
+    let after = try_parse!(parse_capture(&caps, "after"));
 
     ParseResult::Parsed(FGGameMessage::GameStateChanged { before, after })
 }
@@ -53,8 +97,8 @@ fn matchmaking_message_received(input: &str) -> ParseResult<FGGameMessage> {
         let Some(caps) = re.captures(line) else {
             continue;
         };
-        let key = caps.name("key").unwrap().as_str();
-        let value = caps.name("value").unwrap().as_str();
+        let key = try_parse!(capture(&caps, "key"));
+        let value = try_parse!(capture(&caps, "value"));
         match key {
             "name" => match value {
                 "Play" => {
@@ -103,7 +147,9 @@ fn matchmaking_message_received(input: &str) -> ParseResult<FGGameMessage> {
             _ => {}
         }
     }
-    unreachable!()
+    ParseResult::Error(FGParseErrorCause::UnexpectedFormat(
+        "no known matchmaking state".to_owned(),
+    ))
 }
 fn server_connected(input: &str) -> ParseResult<FGGameMessage> {
     if !input.contains("[StateConnectToGame] InitiateNetworkConnectRequest with server IP: ") {
@@ -112,8 +158,10 @@ fn server_connected(input: &str) -> ParseResult<FGGameMessage> {
     let re = create_regex(
         r"InitiateNetworkConnectRequest with server IP: (?<ip>[0-9.]+):(?<port>[0-9]+)?",
     );
-    let captures = re.captures(input).unwrap();
-    let ip_addr = captures.name("ip").unwrap().as_str().to_owned();
+    let Some(captures) = re.captures(input) else {
+        return ParseResult::Error(FGParseErrorCause::MissingCapture("ip"));
+    };
+    let ip_addr = try_parse!(capture(&captures, "ip")).to_owned();
     let port = captures.name("port").map(|m| m.as_str().to_owned());
     ParseResult::Parsed(FGGameMessage::ServerConnected { ip_addr, port })
 }
@@ -125,9 +173,9 @@ fn create_local_player(input: &str) -> ParseResult<FGGameMessage> {
             return ParseResult::None;
         };
 
-        let player_id = caps.name("player_id").unwrap().as_str();
+        let player_id = try_parse!(parse_capture(&caps, "player_id"));
 
-        return ParseResult::Parsed(FGGameMessage::CreateLocalPlayer(player_id.parse().unwrap()));
+        return ParseResult::Parsed(FGGameMessage::CreateLocalPlayer(player_id));
     }
     ParseResult::None
 }
@@ -139,19 +187,19 @@ fn successfully_joined(input: &str) -> ParseResult<FGGameMessage> {
         return ParseResult::NeedMoreLines;
     }
 
-    let Some(selected_show) = create_regex(r"Selected show is (?<selected_show>[a-zA-Z0-9_]+)")
-        .captures(input)
-        .and_then(|caps| caps.name("selected_show"))
+    let Some(selected_show) =
+        create_regex(r"Selected show is (?<selected_show>[a-zA-Z0-9_]+)").captures(input)
     else {
         return ParseResult::None;
     };
+    let game_mode = try_parse!(parse_capture(&selected_show, "selected_show"));
     let session = create_regex(r"Session: (?<session>[a-zA-Z0-9_-]+)")
         .captures(input)
         .and_then(|caps| caps.name("session"))
         .map(|matched| matched.as_str().to_owned());
 
     ParseResult::Parsed(FGGameMessage::SuccessfullyJoined {
-        game_mode: selected_show.as_str().parse().unwrap(),
+        game_mode,
         session_text: session,
     })
 }
@@ -169,7 +217,7 @@ fn network_metrics(input: &str) -> ParseResult<FGGameMessage> {
         .and_then(|caps| caps.name("latency"))
         .map(|matched| matched.as_str().to_owned())
     else {
-        return ParseResult::Unreachable;
+        return ParseResult::Error(FGParseErrorCause::MissingCapture("latency"));
     };
 
     let latency = latency_str.replace(",", "").parse().unwrap_or(-1);
@@ -188,7 +236,7 @@ fn set_client_readiness(input: &str) -> ParseResult<FGGameMessage> {
         .and_then(|caps| caps.name("state"))
         .map(|matched| matched.as_str().to_owned())
     else {
-        return ParseResult::Unreachable;
+        return ParseResult::Error(FGParseErrorCause::MissingCapture("state"));
     };
 
     let state_parsed = state_str
@@ -223,15 +271,12 @@ fn request_local_player(input: &str) -> ParseResult<FGGameMessage> {
     }
 
     let re = create_regex(r"Requesting spawn of local player, ID=(?<id>[0-9]+)");
-    let Some(id_str) = re
-        .captures(input)
-        .and_then(|caps| caps.name("id"))
-        .map(|matched| matched.as_str().to_owned())
-    else {
+    let Some(caps) = re.captures(input) else {
         return ParseResult::None;
     };
+    let player_id = try_parse!(parse_capture(&caps, "id"));
 
-    ParseResult::Parsed(FGGameMessage::RequestLocalPlayer(id_str.parse().unwrap()))
+    ParseResult::Parsed(FGGameMessage::RequestLocalPlayer(player_id))
 }
 
 fn handle_remote_player(input: &str) -> ParseResult<FGGameMessage> {
@@ -247,9 +292,9 @@ fn handle_remote_player(input: &str) -> ParseResult<FGGameMessage> {
         return ParseResult::None;
     };
 
-    let player_id = caps.name("player_id").unwrap().as_str().parse().unwrap();
-    let net_player_id = caps.name("net_id").unwrap().as_str().parse().unwrap();
-    let squad_id = caps.name("squad_id").unwrap().as_str().parse().ok();
+    let player_id = try_parse!(parse_capture(&caps, "player_id"));
+    let net_player_id = try_parse!(parse_capture(&caps, "net_id"));
+    let squad_id = parse_capture(&caps, "squad_id").ok();
 
     ParseResult::Parsed(FGGameMessage::HandleRemotePlayer {
         player_id,
@@ -259,21 +304,21 @@ fn handle_remote_player(input: &str) -> ParseResult<FGGameMessage> {
 }
 
 fn handle_local_player(input: &str) -> ParseResult<FGGameMessage> {
-    if !input.contains("[ClientGameManager] Requesting local player") {
+    if !input.contains("[ClientGameManager] Handling bootstrap for local player ") {
         return ParseResult::None;
     }
 
     let re = create_regex(
-        r"[ClientGameManager] Handling bootstrap for local player (?<player_name>[\s\S]+) \[(?<net_id>[0-9]+)\] \((?<class>[\s\S]+)\), playerID = (?<player_id>[0-9]+), squadID = (?<squad_id>[0-9]+)",
+        r"Handling bootstrap for local player (?<player_name>[\s\S]+) \[(?<net_id>[0-9]+)\] \((?<class>[\s\S]+)\), playerID = (?<player_id>[0-9]+), squadID = (?<squad_id>[0-9]+)",
     );
 
     let Some(caps) = re.captures(input) else {
         return ParseResult::None;
     };
 
-    let player_id = caps.name("player_id").unwrap().as_str().parse().unwrap();
-    let net_player_id = caps.name("net_id").unwrap().as_str().parse().unwrap();
-    let squad_id = caps.name("squad_id").unwrap().as_str().parse().ok();
+    let player_id = try_parse!(parse_capture(&caps, "player_id"));
+    let net_player_id = try_parse!(parse_capture(&caps, "net_id"));
+    let squad_id = parse_capture(&caps, "squad_id").ok();
 
     ParseResult::Parsed(FGGameMessage::HandleLocalPlayer {
         player_id,
@@ -288,25 +333,17 @@ fn append_spectator_target(input: &str) -> ParseResult<FGGameMessage> {
     }
 
     let re = create_regex(
-        r"Adding Spectator target ([\s\S]+) \((?<platform>[\s\S]+)\) with Party ID: (?<party_id>[0-9 ]+) Squad ID: (?<squad_id>[0-9]+) and playerID: (?<player_id>[0-9]+)",
+        r"Adding Spectator target ([\s\S]+) \((?<platform>[\s\S]+)\) with Party ID: (?<party_id>[0-9]*) Squad ID: (?<squad_id>[0-9]+) and playerID: (?<player_id>[0-9]+)",
     );
 
     let Some(caps) = re.captures(input) else {
         return ParseResult::None;
     };
 
-    let player_id = caps.name("player_id").unwrap().as_str().parse().unwrap();
-    let platform = FGPlatform::from_str(caps.name("platform").unwrap().as_str()).unwrap();
-
-    fn wrap_nowhitespace(value: &str) -> Option<String> {
-        let value = value.replace(" ", "");
-        if !value.is_empty() { Some(value) } else { None }
-    }
-
-    let squad_id =
-        wrap_nowhitespace(caps.name("squad_id").unwrap().as_str()).map(|str| str.parse().unwrap());
-    let party_id =
-        wrap_nowhitespace(caps.name("party_id").unwrap().as_str()).map(|str| str.parse().unwrap());
+    let player_id = try_parse!(parse_capture(&caps, "player_id"));
+    let platform = try_parse!(parse_capture(&caps, "platform"));
+    let squad_id = try_parse!(parse_optional_capture(&caps, "squad_id"));
+    let party_id = try_parse!(parse_optional_capture(&caps, "party_id"));
 
     ParseResult::Parsed(FGGameMessage::AppendSpectatorTarget {
         player_id,
@@ -329,13 +366,8 @@ fn player_spawned(input: &str) -> ParseResult<FGGameMessage> {
         return ParseResult::None;
     };
 
-    let player_id = caps.name("player_id").unwrap().as_str().parse().unwrap();
-    let net_player_id = caps
-        .name("net_player_id")
-        .unwrap()
-        .as_str()
-        .parse()
-        .unwrap();
+    let player_id = try_parse!(parse_capture(&caps, "player_id"));
+    let net_player_id = try_parse!(parse_capture(&caps, "net_player_id"));
 
     ParseResult::Parsed(FGGameMessage::PlayerSpawned {
         player_id,
@@ -354,7 +386,7 @@ fn set_local_squad_id(input: &str) -> ParseResult<FGGameMessage> {
         return ParseResult::None;
     };
 
-    let player_id = caps.name("player_id").map(|s| s.as_str().parse().unwrap());
+    let player_id = try_parse!(parse_optional_capture(&caps, "player_id"));
 
     ParseResult::Parsed(FGGameMessage::SetLocalSquadId(player_id))
 }
@@ -370,7 +402,7 @@ fn set_local_party_id(input: &str) -> ParseResult<FGGameMessage> {
         return ParseResult::None;
     };
 
-    let party_id = caps.name("party_id").map(|s| s.as_str().parse().unwrap());
+    let party_id = try_parse!(parse_optional_capture(&caps, "party_id"));
 
     ParseResult::Parsed(FGGameMessage::SetLocalPartyId(party_id))
 }
@@ -392,16 +424,12 @@ fn game_session_state(input: &str) -> ParseResult<FGGameMessage> {
         .map(|s| s.as_str().to_owned())
         .and_then(|str| FGGameSessionState::from_str(&str).ok());
 
-    let after = caps
-        .name("after")
-        .map(|s| s.as_str().to_owned())
-        .and_then(|str| FGGameSessionState::from_str(&str).ok())
-        .unwrap();
+    let after = try_parse!(parse_capture(&caps, "after"));
 
     ParseResult::Parsed(FGGameMessage::GameSessionState { after, before })
 }
 
-fn set_num_players_achieving_objectsive(input: &str) -> ParseResult<FGGameMessage> {
+fn set_num_players_achieving_objective(input: &str) -> ParseResult<FGGameMessage> {
     if !input.contains("[ClientGameSession] NumPlayersAchievingObjective=") {
         return ParseResult::None;
     }
@@ -411,11 +439,7 @@ fn set_num_players_achieving_objectsive(input: &str) -> ParseResult<FGGameMessag
         return ParseResult::None;
     };
 
-    let num_players = caps
-        .name("num_players")
-        .map(|s| s.as_str().to_owned())
-        .and_then(|str| str.parse().ok())
-        .unwrap();
+    let num_players = try_parse!(parse_capture(&caps, "num_players"));
 
     ParseResult::Parsed(FGGameMessage::SetNumPlayersAchievingObjective(num_players))
 }
@@ -431,17 +455,8 @@ fn set_player_score(input: &str) -> ParseResult<FGGameMessage> {
         return ParseResult::None;
     };
 
-    let net_player_id = caps
-        .name("net_player_id")
-        .map(|s| s.as_str().to_owned())
-        .and_then(|str| str.parse().ok())
-        .unwrap();
-
-    let score = caps
-        .name("score")
-        .map(|s| s.as_str().to_owned())
-        .and_then(|str| str.parse().ok())
-        .unwrap();
+    let net_player_id = try_parse!(parse_capture(&caps, "net_player_id"));
+    let score = try_parse!(parse_capture(&caps, "score"));
 
     ParseResult::Parsed(FGGameMessage::SetPlayerScore {
         net_player_id,
@@ -460,11 +475,7 @@ fn handle_unspawn(input: &str) -> ParseResult<FGGameMessage> {
         return ParseResult::None;
     };
 
-    let net_player_id = caps
-        .name("net_player_id")
-        .map(|s| s.as_str().to_owned())
-        .and_then(|str| str.parse().ok())
-        .unwrap();
+    let net_player_id = try_parse!(parse_capture(&caps, "net_player_id"));
 
     ParseResult::Parsed(FGGameMessage::HandleUnspawn(net_player_id))
 }
@@ -482,16 +493,8 @@ fn set_player_progress(input: &str) -> ParseResult<FGGameMessage> {
         return ParseResult::None;
     };
 
-    let player_id = caps
-        .name("player_id")
-        .map(|s| s.as_str())
-        .and_then(|str| str.parse().ok())
-        .unwrap();
-
-    let is_succeeded = caps
-        .name("is_succeeded")
-        .map(|s| s.as_str().to_owned())
-        .unwrap();
+    let player_id = try_parse!(parse_capture(&caps, "player_id"));
+    let is_succeeded = try_parse!(capture(&caps, "is_succeeded"));
 
     ParseResult::Parsed(FGGameMessage::SetPlayerProgress {
         player_id,
@@ -518,7 +521,7 @@ fn game_lobby_rewards(input: &str) -> ParseResult<FGGameMessage> {
     };
     if !is_out_of_scope(input) {
         // We assume that the first blank line(not valid log)'s length are same thus, we can use it as a reference.
-        let last_line = input.lines().last().unwrap();
+        let last_line = input.lines().last().unwrap_or_default();
         if is_valid_log(last_line)
             // Parse first 6 line (which is always shown.) then extra 2 for round #0.
             || input.lines().count() < (6 + 2)
@@ -528,9 +531,7 @@ fn game_lobby_rewards(input: &str) -> ParseResult<FGGameMessage> {
                     .filter(|line| !is_valid_log(line))
                     .map(|line| line.len())
                     .min()
-                    .unwrap()
-                    .abs_diff(last_line.len())
-                    < 5)
+                    .is_some_and(|min_len| min_len.abs_diff(last_line.len()) < 5))
         {
             return ParseResult::NeedMoreLines;
         }
@@ -559,56 +560,70 @@ fn game_lobby_rewards(input: &str) -> ParseResult<FGGameMessage> {
                 temp_round = generate_fg_completed_episode_dto_round();
             }
             let Some(caps) = title_regex.captures(line) else {
-                println!("line: {}", line);
-                return ParseResult::Unreachable;
+                return ParseResult::Error(FGParseErrorCause::UnexpectedFormat(format!(
+                    "unknown round title `{}`",
+                    line
+                )));
             };
-            let order = caps.name("order").unwrap().as_str().parse().unwrap();
-            let round_id_str = caps.name("round_id_str").unwrap().as_str().to_string();
+            let order = try_parse!(parse_capture(&caps, "order"));
+            let round_id_str = try_parse!(capture(&caps, "round_id_str")).to_string();
             round_order = order;
             temp_round.round_order = order;
             temp_round.round_id_str = round_id_str.clone();
             temp_round.round_display_name = localized_string_round_id(&round_id_str);
         } else if line.contains("> ") && line.contains(": ") {
             let Some(caps) = prop_regex.captures(line) else {
-                println!("line: {}", line);
-                return ParseResult::Unreachable;
+                return ParseResult::Error(FGParseErrorCause::UnexpectedFormat(format!(
+                    "unknown property `{}`",
+                    line
+                )));
             };
-            let key = caps.name("key").unwrap().as_str();
-            let value = caps.name("value").unwrap().as_str();
+            let key = try_parse!(capture(&caps, "key"));
+            let value = try_parse!(capture(&caps, "value"));
 
             if round_order == -1 {
                 match key {
-                    "Kudos" => kudos = Some(value.parse().unwrap()),
-                    "Fame" => fame = Some(value.parse().unwrap()),
-                    "Crowns" => crowns = Some(value.parse().unwrap()),
-                    "CurrentCrownShards" => current_crown_shards = Some(value.parse().unwrap()),
+                    "Kudos" => kudos = Some(try_parse!(parse_value("Kudos", value))),
+                    "Fame" => fame = Some(try_parse!(parse_value("Fame", value))),
+                    "Crowns" => crowns = Some(try_parse!(parse_value("Crowns", value))),
+                    "CurrentCrownShards" => {
+                        current_crown_shards =
+                            Some(try_parse!(parse_value("CurrentCrownShards", value)))
+                    }
                     _ => {}
                 }
             } else {
                 if value.is_empty() {
-                    println!("DTO: {key} is empty value.");
+                    debug!("DTO: {key} is empty value.");
                     continue;
                 }
                 match key {
-                    "Qualified" => {
-                        temp_round.qualified = value.parse::<String>().unwrap() == "True"
+                    "Qualified" => temp_round.qualified = value == "True",
+                    "Position" => temp_round.position = try_parse!(parse_value("Position", value)),
+                    "Team Score" => {
+                        temp_round.team_score = try_parse!(parse_value("Team Score", value))
+                    }
+                    "Kudos" => temp_round.kudos = try_parse!(parse_value("Kudos", value)),
+                    "Fame" => temp_round.fame = try_parse!(parse_value("Fame", value)),
+                    "Bonus Tier" => {
+                        temp_round.bonus_tier = try_parse!(parse_value("Bonus Tier", value))
                     }
-                    "Position" => temp_round.position = value.parse().unwrap(),
-                    "Team Score" => temp_round.team_score = value.parse().unwrap(),
-                    "Kudos" => temp_round.kudos = value.parse().unwrap(),
-                    "Fame" => temp_round.fame = value.parse().unwrap(),
-                    "Bonus Tier" => temp_round.bonus_tier = value.parse().unwrap(),
-                    "Bonus Kudos" => temp_round.bonus_kudos = value.parse().unwrap(),
-                    "Bonus Fame" => temp_round.bonus_fame = value.parse().unwrap(),
-                    "BadgeId" => temp_round.badge_id = value.parse().unwrap(),
-                    _ => unreachable!("Unknown key value {key}"),
+                    "Bonus Kudos" => {
+                        temp_round.bonus_kudos = try_parse!(parse_value("Bonus Kudos", value))
+                    }
+                    "Bonus Fame" => {
+                        temp_round.bonus_fame = try_parse!(parse_value("Bonus Fame", value))
+                    }
+                    "BadgeId" => temp_round.badge_id = try_parse!(parse_value("BadgeId", value)),
+                    // New keys after a game patch should not drop the whole rewards.
+                    _ => warn!("DTO: unknown key {key}"),
                 }
             }
         }
     }
 
-    // Push the last rounds if not same.
-    if !rounds.is_empty() && rounds.last().unwrap().round_order != temp_round.round_order {
+    // Push the last round, which has no title after it.
+    if round_order != -1 {
         rounds.push(temp_round);
     }
     ParseResult::Parsed(FGGameMessage::GameLobbyRewards(FGCompletedEpisodeDto {
@@ -627,7 +642,7 @@ fn creative_round_loader(input: &str) -> ParseResult<FGGameMessage> {
 
     let re = create_regex(r" Load UGC via share code: (?<code>[0-9-]+):(?<version>[0-9]+)");
     if let Some(captures) = re.captures(input) {
-        let code = captures["code"].parse().unwrap();
+        let code = try_parse!(capture(&captures, "code")).to_owned();
         ParseResult::Parsed(FGGameMessage::CreativeRoundLoader(code))
     } else {
         ParseResult::None
@@ -670,37 +685,141 @@ fn server_message_end_round(input: &str) -> ParseResult<FGGameMessage> {
     ParseResult::Parsed(FGGameMessage::ServerMessageEndRound)
 }
 
-pub type FGParserRule = Arc<dyn Fn(&str) -> ParseResult<FGGameMessage> + Send + Sync>;
+pub type FGParserRuleFn = Arc<dyn Fn(&str) -> ParseResult<FGGameMessage> + Send + Sync>;
+
+/// The parser rule with its name, which is used for reporting errors.
+#[derive(Clone)]
+pub struct FGParserRule {
+    pub name: &'static str,
+    /// The text every line handled by the rule contains, used to dispatch the line.
+    /// The rule is not called with the lines without it, except the ones it requested.
+    pub anchor: &'static str,
+    pub parse: FGParserRuleFn,
+}
+
+impl std::fmt::Debug for FGParserRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FGParserRule")
+            .field("name", &self.name)
+            .field("anchor", &self.anchor)
+            .finish_non_exhaustive()
+    }
+}
+
+macro_rules! rule {
+    ($func:ident, $anchor:expr) => {
+        FGParserRule {
+            name: stringify!($func),
+            anchor: $anchor,
+            parse: Arc::new($func),
+        }
+    };
+}
+
 pub fn rules() -> Vec<FGParserRule> {
     vec![
-        Arc::new(game_state_changed),
-        Arc::new(begin_matchmaking),
-        Arc::new(matchmaking_message_received),
-        Arc::new(server_connected),
-        Arc::new(create_local_player),
-        Arc::new(successfully_joined),
-        Arc::new(network_metrics),
-        Arc::new(set_client_readiness),
-        Arc::new(loaded_round),
-        Arc::new(request_local_player),
-        Arc::new(handle_remote_player),
-        Arc::new(handle_local_player),
-        Arc::new(append_spectator_target),
-        Arc::new(player_spawned),
-        Arc::new(set_local_squad_id),
-        Arc::new(set_local_party_id),
-        Arc::new(game_session_state),
-        Arc::new(set_num_players_achieving_objectsive),
-        Arc::new(set_player_score),
-        Arc::new(handle_unspawn),
-        Arc::new(set_player_progress),
-        Arc::new(game_lobby_rewards),
-        Arc::new(creative_round_loader),
-        Arc::new(leave_match),
-        Arc::new(round_over),
-        Arc::new(server_message_start_loading_level),
-        Arc::new(server_message_ready_round_response),
-        Arc::new(server_message_round_results),
-        Arc::new(server_message_end_round),
+        rule!(game_state_changed, "[GameStateMachine] Replacing "),
+        rule!(begin_matchmaking, "[Matchmaking] Begin matchmaking"),
+        rule!(
+            matchmaking_message_received,
+            "[FNMMSClientRemoteService] Status message received:"
+        ),
+        rule!(
+            server_connected,
+            "[StateConnectToGame] InitiateNetworkConnectRequest with server IP: "
+        ),
+        rule!(
+            create_local_player,
+            "[CreateLocalPlayerInstances] Added new player as Participant"
+        ),
+        rule!(
+            successfully_joined,
+            "[HandleSuccessfulLogin] Selected show is "
+        ),
+        rule!(
+            network_metrics,
+            "[FG_UnityInternetNetworkManager] Networking Metrics after"
+        ),
+        rule!(
+            set_client_readiness,
+            "[ClientGameManager] Setting this client as readiness state"
+        ),
+        rule!(
+            loaded_round,
+            "[StateGameLoading] Finished loading game level,"
+        ),
+        rule!(
+            request_local_player,
+            "Requesting spawn of local player, ID="
+        ),
+        rule!(
+            handle_remote_player,
+            "[ClientGameManager] Handling bootstrap for remote player "
+        ),
+        rule!(
+            handle_local_player,
+            "[ClientGameManager] Handling bootstrap for local player "
+        ),
+        rule!(
+            append_spectator_target,
+            "[CameraDirector] Adding Spectator target"
+        ),
+        rule!(player_spawned, "[StateGameLoading] OnPlayerSpawned - NetID"),
+        rule!(set_local_squad_id, "[CameraDirector] Set Local Squad ID: "),
+        rule!(set_local_party_id, "[CameraDirector] Set Local Party ID: "),
+        rule!(game_session_state, "[GameSession] Changing state from "),
+        rule!(
+            set_num_players_achieving_objective,
+            "[ClientGameSession] NumPlayersAchievingObjective="
+        ),
+        rule!(set_player_score, " score = "),
+        rule!(
+            handle_unspawn,
+            "[ClientGameManager] Handling unspawn for player "
+        ),
+        rule!(
+            set_player_progress,
+            "ClientGameManager::HandleServerPlayerProgress PlayerId="
+        ),
+        rule!(game_lobby_rewards, " [CompletedEpisodeDto] "),
+        rule!(
+            creative_round_loader,
+            "[RoundLoader] Load UGC via share code: "
+        ),
+        rule!(
+            leave_match,
+            "[LeaveMatchPopupManager] Calling CloseScreen()"
+        ),
+        rule!(
+            round_over,
+            "[ClientGameManager] Server notifying that the round is over."
+        ),
+        rule!(
+            server_message_start_loading_level,
+            "GameMessageServerStartLoadingLevel received"
+        ),
+        rule!(
+            server_message_ready_round_response,
+            "GameMessageServerReadyRoundResponse received"
+        ),
+        rule!(
+            server_message_round_results,
+            "GameMessageServerRoundResults received"
+        ),
+        rule!(
+            server_message_end_round,
+            "GameMessageServerEndRound received"
+        ),
     ]
 }
+
+#[test]
+fn invalid_value_is_error() {
+    assert_eq!(
+        set_player_score("10:00:00.000: Player 999999999999999999999999 score = 5"),
+        ParseResult::Error(FGParseErrorCause::InvalidValue {
+            field: "net_player_id",
+            value: "999999999999999999999999".to_owned(),
+        })
+    );
+}
@@ -1,119 +1,65 @@
-use temporal_rs::{Instant, PlainTime, Temporal};
+use temporal_rs::Instant;
 use tokio::sync::mpsc::{self, Receiver};
-use tracing::warn;
+use tracing::error;
 
 use crate::models::messages::FGGameMessage;
 
-use super::{ParseResult, create_regex, rules::FGParserRule};
+use super::{FGLogInput, log_clock::FGLogClock, log_parser::FGLogParser};
 
+/// Runs `FGLogParser` over the input from the log readers.
 pub async fn parse_from_str_rx(
-    mut str_rx: Receiver<String>,
+    mut str_rx: Receiver<FGLogInput>,
+    clock: FGLogClock,
 ) -> Receiver<(FGGameMessage, Option<Instant>)> {
     let (tx, rx) = mpsc::channel(1024);
 
     tokio::spawn(async move {
-        let mut need_more_lines_rule: Option<FGParserRule> = None;
-        let mut temp_buffer: String = String::new();
-        while let Some(mut str) = str_rx.recv().await {
-            let rules = if need_more_lines_rule.is_some() {
-                let mut buf = temp_buffer;
-                buf.push('\n');
-                buf.push_str(&str);
-                temp_buffer = buf.clone();
-                str = buf;
-
-                vec![need_more_lines_rule.clone().unwrap()]
-            } else {
-                super::rules::rules()
-            };
-            
-            if temp_buffer.lines().count() > 100 {
-                unreachable!("Parser just read 100 lines of buffer, which should not happen.");
-            }
-
-            for rule in rules {
-                let eval = rule(&str);
-                match eval {
-                    ParseResult::Parsed(data) => {
-                        tx.send((data, try_parse_log_time(&str))).await.unwrap();
-                        need_more_lines_rule = None;
-                        temp_buffer = String::new();
-                        break;
-                    }
-                    ParseResult::NeedMoreLines => {
-                        need_more_lines_rule = Some(rule);
-                        temp_buffer = str;
-                        break;
-                    }
-                    ParseResult::Unreachable => {
-                        println!("Unreachable state reached");
-                        unreachable!();
-                    }
-                    ParseResult::None => {
-                        if need_more_lines_rule.is_some() || !temp_buffer.is_empty() {
-                            need_more_lines_rule = None;
-                            temp_buffer = String::new();
-                        }
-                    }
+        let mut parser = FGLogParser::new(clock);
+        while let Some(input) = str_rx.recv().await {
+            let parsed = match parser.feed(input) {
+                Some(Ok(parsed)) => parsed,
+                // Report then skip the line(s), so the pipeline keeps running.
+                Some(Err(err)) => {
+                    error!("{}", err);
+                    (FGGameMessage::ParseFailed((&err).into()), None)
                 }
+                None => continue,
+            };
+            if tx.send(parsed).await.is_err() {
+                break;
             }
         }
     });
     rx
 }
 
-/// Ensures the log time does not differ between time ranges.
-pub fn try_parse_log_time(log: &str) -> Option<Instant> {
-    let re = create_regex(
-        r"(?<hours>[0-9]+):(?<minutes>[0-9]+):(?<seconds>[0-9]+).(?<milliseconds>[0-9]+):",
-    );
+#[tokio::test]
+async fn forward_parse_errors() {
+    use crate::test_support::test_clock;
 
-    for line in log.lines() {
-        let Some(caps) = re.captures(line) else {
-            continue;
-        };
-        // 09:14:00.422
-        let hours = caps.name("hours").unwrap().as_str().parse::<u8>().unwrap();
-        let minutes = caps
-            .name("minutes")
-            .unwrap()
-            .as_str()
-            .parse::<u8>()
+    let clock = test_clock();
+    let (str_tx, str_rx) = mpsc::channel(4);
+    let mut rx = parse_from_str_rx(str_rx, clock).await;
+    for line in [
+        "10:00:00.000: [StateConnectToGame] InitiateNetworkConnectRequest with server IP: ?",
+        "10:00:01.000: [Matchmaking] Begin matchmaking solo",
+    ] {
+        str_tx
+            .send(FGLogInput::Line(line.to_owned()))
+            .await
             .unwrap();
-        let seconds = caps
-            .name("seconds")
-            .unwrap()
-            .as_str()
-            .parse::<u8>()
-            .unwrap();
-        let milliseconds = caps
-            .name("milliseconds")
-            .unwrap()
-            .as_str()
-            .parse::<u16>()
-            .unwrap();
-
-        // Let temporal-rs handle the timezone information.
-        let zoned_date_time = Temporal::now().zoned_date_time_iso(None).unwrap();
-        let Ok(time) = PlainTime::new(hours, minutes, seconds, milliseconds, 0, 0) else {
-            warn!(
-                "Could not create PlainTime which is {}:{}:{}:{}",
-                hours, minutes, seconds, milliseconds
-            );
-            continue;
-        };
-        zoned_date_time.with_plain_time(Some(time.clone())).unwrap();
-        let Ok(zoned_date_time) = zoned_date_time.with_plain_time(Some(time)) else {
-            warn!(
-                "Could not call ZonedDateTime::with_plain_time which is {}:{}:{}:{}",
-                hours, minutes, seconds, milliseconds
-            );
-            continue;
-        };
-
-        let instant = zoned_date_time.to_instant();
-        return Some(instant);
     }
+    drop(str_tx);
 
-    None
+    let Some((FGGameMessage::ParseFailed(diagnostic), None)) = rx.recv().await else {
+        panic!("the parse error should be forwarded");
+    };
+    assert_eq!(diagnostic.rule, "server_connected");
+    assert_eq!(diagnostic.cause, "missing capture `ip`");
+    // The pipeline keeps running.
+    assert!(matches!(
+        rx.recv().await,
+        Some((FGGameMessage::BeginMatchmaking, Some(_)))
+    ));
+    assert!(rx.recv().await.is_none());
 }
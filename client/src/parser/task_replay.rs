@@ -0,0 +1,74 @@
+use std::path::PathBuf;
+
+use tokio::{
+    fs::File,
+    io::{AsyncBufReadExt, BufReader},
+    sync::mpsc::{self, Receiver},
+};
+use tracing::{error, info};
+
+use super::{FGLogInput, log_clock::file_anchor};
+
+/// Reads the whole log files in given order, then stream into single line.
+///
+/// Unlike `task_watch::read_log_file`, this closes the channel after the last file,
+/// so the rest of the pipeline finishes as well.
+pub async fn replay_log_files(file_paths: Vec<PathBuf>) -> Receiver<FGLogInput> {
+    let (tx, rx) = mpsc::channel(1024);
+
+    tokio::spawn(async move {
+        for file_path in file_paths {
+            let file = match File::open(&file_path).await {
+                Ok(file) => file,
+                Err(err) => {
+                    error!(
+                        "Could not open the log file {}: {}",
+                        file_path.display(),
+                        err
+                    );
+                    continue;
+                }
+            };
+            info!("Replaying {}", file_path.display());
+            let anchor = match file.metadata().await {
+                Ok(metadata) => file_anchor(&metadata),
+                Err(_) => None,
+            };
+            // Replayed files are not reported as rotated, the order is up to the caller.
+            let started = FGLogInput::FileStarted {
+                anchor,
+                rotation: None,
+            };
+            if tx.send(started).await.is_err() {
+                return;
+            }
+
+            let mut reader = BufReader::new(file);
+            let mut buf = Vec::new();
+            loop {
+                buf.clear();
+                match reader.read_until(b'\n', &mut buf).await {
+                    Ok(0) => break,
+                    Ok(_) => {}
+                    Err(err) => {
+                        error!(
+                            "Could not read the log file {}: {}",
+                            file_path.display(),
+                            err
+                        );
+                        break;
+                    }
+                }
+
+                // Old logs are not always valid UTF-8, don't stop replaying for it.
+                let line = String::from_utf8_lossy(&buf);
+                let line = line.trim_end_matches(['\n', '\r']).to_owned();
+                if tx.send(FGLogInput::Line(line)).await.is_err() {
+                    return;
+                }
+            }
+        }
+    });
+
+    rx
+}
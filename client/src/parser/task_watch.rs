@@ -1,14 +1,31 @@
 use notify::{
-    Config, EventKind, RecommendedWatcher, Watcher,
-    event::{AccessKind, CreateKind, ModifyKind},
+    Config, EventKind, RecommendedWatcher, RecursiveMode, Watcher,
+    event::{AccessKind, CreateKind, ModifyKind, RemoveKind, RenameMode},
 };
-use std::io::SeekFrom;
+use serde_with::{DeserializeFromStr, SerializeDisplay};
+use std::{
+    fmt::Display,
+    fs::Metadata,
+    io::SeekFrom,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{Duration, SystemTime},
+};
+use temporal_rs::Temporal;
 use tokio::{
-    fs::OpenOptions,
-    sync::mpsc::{self, Receiver},
+    fs::{File, OpenOptions},
+    sync::mpsc::{self, Receiver, Sender},
+    time::{interval, sleep},
 };
 
-use tokio::io::{AsyncBufReadExt, AsyncSeekExt, BufReader};
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+use tracing::{error, info, warn};
+
+use super::{
+    FGLogInput,
+    log_clock::{FGLogAnchor, file_anchor},
+};
+use crate::models::messages::FGLogRotation;
 
 /*
 * Fallguys IO state on Linux/Steam/Proton.
@@ -23,15 +40,60 @@ use tokio::io::{AsyncBufReadExt, AsyncSeekExt, BufReader};
 * - Create(File)
 * - Modify(Data(Any))
 * - Access(Close(Write))
+*
+* So the game renames Player.log to Player-prev.log on start, then creates a new Player.log.
 */
 
+/// Native events missing for this many polls in a row switch `FGWatchMode::Auto` to polling.
+const FALLBACK_MISSED_POLLS: usize = 3;
+
+/// How to watch the log file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, SerializeDisplay, DeserializeFromStr)]
+pub enum FGWatchMode {
+    /// Native events, falling back to polling when they are missing.
+    #[default]
+    Auto,
+    /// Native events only, e.g. inotify.
+    Native,
+    /// Polling the file metadata, for SD cards, bind mounts or network shares.
+    Poll,
+}
+
+impl FromStr for FGWatchMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(FGWatchMode::Auto),
+            "native" => Ok(FGWatchMode::Native),
+            "poll" => Ok(FGWatchMode::Poll),
+            _ => Err(format!(
+                "unknown watch mode `{}`, expected auto, native or poll",
+                s
+            )),
+        }
+    }
+}
+
+impl Display for FGWatchMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mode = match self {
+            FGWatchMode::Auto => "auto",
+            FGWatchMode::Native => "native",
+            FGWatchMode::Poll => "poll",
+        };
+        write!(f, "{}", mode)
+    }
+}
+
 /// Creates a async watcher in sync runtime.
 pub fn async_watcher()
 -> notify::Result<(RecommendedWatcher, Receiver<notify::Result<notify::Event>>)> {
     let (tx, rx) = mpsc::channel(1024);
     let watcher = RecommendedWatcher::new(
         move |res| {
-            tx.blocking_send(res).unwrap();
+            // The receiver is gone when the reader has been stopped.
+            let _ = tx.blocking_send(res);
         },
         Config::default(),
     )?;
@@ -39,50 +101,239 @@ pub fn async_watcher()
     Ok((watcher, rx))
 }
 
-pub async fn watch_dir(dir_path: &str, file_name: &str) -> Receiver<WatchMessage> {
-    let (tx, rx) = mpsc::channel(1024);
-    let (mut watcher, mut watch_rx) = async_watcher().unwrap();
+type FGNativeWatcher = (RecommendedWatcher, Receiver<notify::Result<notify::Event>>);
+
+fn start_native_watcher(dir_path: &Path) -> notify::Result<FGNativeWatcher> {
+    let (mut watcher, rx) = async_watcher()?;
+    watcher.watch(dir_path, RecursiveMode::NonRecursive)?;
+    Ok((watcher, rx))
+}
+
+/// Waits for the next native event, or forever if not watching natively.
+async fn recv_native_event(
+    native: &mut Option<FGNativeWatcher>,
+) -> Option<notify::Result<notify::Event>> {
+    match native {
+        Some((_, rx)) => rx.recv().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Waits for the directory, the prefix may not be created or mounted yet.
+async fn wait_for_dir(dir_path: &Path, poll_interval: Duration) {
+    let mut logged = false;
+    while !tokio::fs::metadata(dir_path)
+        .await
+        .is_ok_and(|metadata| metadata.is_dir())
+    {
+        if !logged {
+            info!("Waiting for the directory {} to appear", dir_path.display());
+            logged = true;
+        }
+        sleep(poll_interval).await;
+    }
+}
 
-    watcher
-        .watch(dir_path.as_ref(), notify::RecursiveMode::NonRecursive)
-        .unwrap();
+fn native_message(event: &notify::Event, file_name: &str) -> Option<WatchMessage> {
+    // Renaming has the path before the rename first.
+    let path = event.paths.first()?;
+    if !path.ends_with(file_name) {
+        return None;
+    }
+
+    match event.kind {
+        EventKind::Access(AccessKind::Close(_)) => Some(WatchMessage::Closed),
+        EventKind::Create(CreateKind::File)
+        | EventKind::Modify(ModifyKind::Name(RenameMode::To)) => Some(WatchMessage::FileCreated),
+        EventKind::Modify(ModifyKind::Data(_)) => Some(WatchMessage::ContentModified),
+        EventKind::Modify(ModifyKind::Name(RenameMode::From | RenameMode::Both)) => {
+            Some(WatchMessage::Renamed)
+        }
+        EventKind::Remove(RemoveKind::File) => Some(WatchMessage::Removed),
+        _ => None,
+    }
+}
+
+/// The metadata compared between polls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FGFileStat {
+    file_id: u64,
+    len: u64,
+    modified: Option<SystemTime>,
+}
+
+async fn stat_file(path: &Path) -> Option<FGFileStat> {
+    let metadata = tokio::fs::metadata(path).await.ok()?;
+    Some(FGFileStat {
+        file_id: file_id(&metadata),
+        len: metadata.len(),
+        modified: metadata.modified().ok(),
+    })
+}
+
+/// Identifies the file regardless of its name, to tell a rotation from a modification.
+#[cfg(unix)]
+fn file_id(metadata: &Metadata) -> u64 {
+    std::os::unix::fs::MetadataExt::ino(metadata)
+}
 
-    let file_path = format!("{}/{}", dir_path, file_name);
-    let dir_path = dir_path.to_owned();
+/// The creation time stands in for the inode, it is kept by renaming but not by truncating.
+/// The length is not part of it as the file grows.
+///
+/// Windows may give a file created right after a rename the creation time of the renamed one,
+/// which is then taken for a modification, see "file system tunneling".
+#[cfg(not(unix))]
+fn file_id(metadata: &Metadata) -> u64 {
+    metadata
+        .created()
+        .ok()
+        .and_then(|created| created.duration_since(SystemTime::UNIX_EPOCH).ok())
+        .map_or(0, |created| created.as_nanos() as u64)
+}
+
+/// Whether the file is still in the directory under another name.
+async fn is_in_dir(dir_path: &Path, file_id_to_find: u64) -> bool {
+    let Ok(mut entries) = tokio::fs::read_dir(dir_path).await else {
+        return false;
+    };
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        if let Ok(metadata) = entry.metadata().await
+            && file_id(&metadata) == file_id_to_find
+        {
+            return true;
+        }
+    }
+    false
+}
+
+/// The messages for the change between two polls.
+/// `kept` is whether the file polled before is still in the directory under another name.
+fn poll_messages(
+    before: Option<FGFileStat>,
+    after: Option<FGFileStat>,
+    kept: bool,
+) -> Vec<WatchMessage> {
+    let rotated = if kept {
+        WatchMessage::Renamed
+    } else {
+        WatchMessage::Removed
+    };
+    match (before, after) {
+        (None, None) => Vec::new(),
+        (None, Some(_)) => vec![WatchMessage::FileCreated],
+        (Some(_), None) => vec![rotated],
+        (Some(before), Some(after)) if before.file_id != after.file_id => {
+            vec![rotated, WatchMessage::FileCreated]
+        }
+        (Some(before), Some(after)) if before != after => vec![WatchMessage::ContentModified],
+        _ => Vec::new(),
+    }
+}
+
+/// Watches the file in the directory, waiting for the directory to appear first.
+///
+/// The existing file is reported as modified at first, so it is read from the start.
+pub async fn watch_dir(
+    dir_path: &str,
+    file_name: &str,
+    mode: FGWatchMode,
+    poll_interval: Duration,
+) -> Receiver<WatchMessage> {
+    let (tx, rx) = mpsc::channel(1024);
+
+    let dir_path = PathBuf::from(dir_path);
+    let file_path = dir_path.join(file_name);
     let file_name = file_name.to_owned();
     tokio::spawn(async move {
-        while let Some(event) = watch_rx.recv().await {
-            // Just panic if event is Err.
-            let event = event.unwrap();
-
-            if event.need_rescan() {
-                // Re-watch if required.
-                watcher
-                    .watch(dir_path.as_ref(), notify::RecursiveMode::NonRecursive)
-                    .unwrap();
-                println!("Rescanning")
+        wait_for_dir(&dir_path, poll_interval).await;
+
+        let mut native = None;
+        if mode != FGWatchMode::Poll {
+            match start_native_watcher(&dir_path) {
+                Ok(watcher) => native = Some(watcher),
+                Err(err) if mode == FGWatchMode::Auto => {
+                    warn!(
+                        "Could not watch {}, polling it instead: {}",
+                        dir_path.display(),
+                        err
+                    )
+                }
+                Err(err) => {
+                    error!("Could not watch {}: {}", dir_path.display(), err);
+                    return;
+                }
             }
+        }
 
-            if let Some(path) = event.paths.first() {
-                if path.ends_with(&file_name) {
-                    let msg = match event.kind {
-                        EventKind::Access(AccessKind::Close(_)) => Some(WatchMessage::Closed),
-                        EventKind::Create(CreateKind::File) => Some(WatchMessage::FileCreated),
-                        EventKind::Modify(ModifyKind::Data(_)) => {
-                            let file = OpenOptions::new()
-                                .read(true)
-                                .open(&file_path)
-                                .await
-                                .unwrap();
-                            let file_len = file.metadata().await.unwrap().len();
-                            Some(WatchMessage::ContentModified { length: file_len })
+        let mut last_stat = stat_file(&file_path).await;
+        if last_stat.is_some() && tx.send(WatchMessage::ContentModified).await.is_err() {
+            return;
+        }
+
+        let mut ticker = interval(poll_interval);
+        // Whether a native event of the log file has been received since the last poll.
+        let mut native_seen = false;
+        let mut missed_polls = 0;
+        loop {
+            tokio::select! {
+                event = recv_native_event(&mut native) => {
+                    let event = match event {
+                        Some(Ok(event)) => event,
+                        Some(Err(err)) => {
+                            error!("Could not watch the log directory: {}", err);
+                            continue;
+                        }
+                        None => {
+                            native = None;
+                            continue;
                         }
-                        _ => None,
                     };
 
-                    if let Some(msg) = msg {
-                        tx.send(msg).await.unwrap();
+                    if event.need_rescan()
+                        && let Some((watcher, _)) = &mut native
+                    {
+                        // Re-watch if required.
+                        if let Err(err) = watcher.watch(&dir_path, RecursiveMode::NonRecursive) {
+                            error!("Could not re-watch the log directory: {}", err);
+                        }
+                        info!("Rescanning");
+                    }
+
+                    if let Some(msg) = native_message(&event, &file_name) {
+                        native_seen = true;
+                        if tx.send(msg).await.is_err() {
+                            return;
+                        }
+                    }
+                }
+                _ = ticker.tick(), if mode != FGWatchMode::Native => {
+                    let stat = stat_file(&file_path).await;
+                    if stat != last_stat && (native.is_none() || !native_seen) {
+                        let kept = match last_stat {
+                            Some(last_stat) => is_in_dir(&dir_path, last_stat.file_id).await,
+                            None => false,
+                        };
+                        for msg in poll_messages(last_stat, stat, kept) {
+                            if tx.send(msg).await.is_err() {
+                                return;
+                            }
+                        }
+
+                        if native.is_some() {
+                            missed_polls += 1;
+                            if missed_polls >= FALLBACK_MISSED_POLLS {
+                                warn!(
+                                    "No file system events for {}, polling it instead",
+                                    file_path.display()
+                                );
+                                native = None;
+                            }
+                        }
+                    } else if native_seen {
+                        missed_polls = 0;
                     }
+                    native_seen = false;
+                    last_stat = stat;
                 }
             }
         }
@@ -94,49 +345,377 @@ pub async fn watch_dir(dir_path: &str, file_name: &str) -> Receiver<WatchMessage
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum WatchMessage {
     FileCreated,
-    ContentModified { length: u64 },
+    ContentModified,
+    /// The log file has been renamed to another name, e.g. `Player-prev.log`.
+    Renamed,
+    Removed,
     Closed,
 }
 
+/// The log file being read, kept open so it can be drained after being renamed.
+struct FGLogTail {
+    file: File,
+    /// The bytes of the complete lines read so far.
+    offset: u64,
+}
+
+impl FGLogTail {
+    /// Opens the log file from the start, with when it has been started.
+    async fn open(file_path: &str) -> std::io::Result<(Self, Option<FGLogAnchor>)> {
+        let file = OpenOptions::new().read(true).open(file_path).await?;
+        let anchor = file_anchor(&file.metadata().await?);
+        Ok((Self { file, offset: 0 }, anchor))
+    }
+
+    /// Whether the path is still the file being read, e.g. reported as created twice.
+    async fn is_file_at(&self, path: &str) -> bool {
+        match (self.file.metadata().await, tokio::fs::metadata(path).await) {
+            (Ok(current), Ok(at_path)) => file_id(&current) == file_id(&at_path),
+            _ => false,
+        }
+    }
+
+    async fn is_truncated(&self) -> std::io::Result<bool> {
+        Ok(self.file.metadata().await?.len() < self.offset)
+    }
+
+    /// Reads the lines completed since the last read.
+    ///
+    /// The game may have flushed only a part of the last line, so it is read again next time,
+    /// unless `to_end` is set because nothing will be written to the file anymore.
+    async fn read_lines(&mut self, to_end: bool) -> std::io::Result<Vec<String>> {
+        self.file.seek(SeekFrom::Start(self.offset)).await?;
+        let mut buf = Vec::new();
+        self.file.read_to_end(&mut buf).await?;
+
+        let (mut lines, consumed) = split_lines(&buf);
+        if to_end && consumed < buf.len() {
+            lines.push(decode_line(&buf[consumed..]));
+            self.offset += buf.len() as u64;
+        } else {
+            self.offset += consumed as u64;
+        }
+        Ok(lines)
+    }
+
+    /// Sends the lines completed since the last read, returns false if the receiver is gone.
+    async fn send_lines(&mut self, tx: &Sender<FGLogInput>, to_end: bool) -> bool {
+        let lines = match self.read_lines(to_end).await {
+            Ok(lines) => lines,
+            Err(err) => {
+                error!("Could not read the log file: {}", err);
+                return true;
+            }
+        };
+        for line in lines {
+            if tx.send(FGLogInput::Line(line)).await.is_err() {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Splits the bytes into the lines terminated by `\n`, returns them with the bytes used.
+fn split_lines(buf: &[u8]) -> (Vec<String>, usize) {
+    let mut lines = Vec::new();
+    let mut consumed = 0;
+    while let Some(end) = buf[consumed..].iter().position(|byte| *byte == b'\n') {
+        lines.push(decode_line(&buf[consumed..consumed + end]));
+        consumed += end + 1;
+    }
+    (lines, consumed)
+}
+
+/// Logs of the game running on Wine may have CRLF, or broken UTF-8 from the player names.
+fn decode_line(line: &[u8]) -> String {
+    let line = line.strip_suffix(b"\r").unwrap_or(line);
+    String::from_utf8_lossy(line).into_owned()
+}
+
 /// Reads the log file and then stream into single line.
+///
+/// When the log file has been renamed, removed or truncated, the rest of the old one is read first,
+/// then the new one is read from the start with `FGLogInput::FileStarted` having the rotation.
 pub async fn read_log_file(
     mut watch_rx: Receiver<WatchMessage>,
     file_path: &str,
-) -> Receiver<String> {
+) -> Receiver<FGLogInput> {
     let (tx, rx) = mpsc::channel(1024);
 
     let file_path = file_path.to_owned();
     tokio::spawn(async move {
-        let mut buffer = 0;
+        let mut tail: Option<FGLogTail> = None;
+        // Why the previous file has been closed, sent when the next one is started.
+        let mut rotation: Option<FGLogRotation> = None;
 
         while let Some(watch_msg) = watch_rx.recv().await {
-            if watch_msg == WatchMessage::FileCreated {
-                buffer = 0;
-            }
+            match watch_msg {
+                WatchMessage::ContentModified | WatchMessage::FileCreated => {
+                    if watch_msg == WatchMessage::FileCreated
+                        && let Some(current_tail) = &tail
+                        && !current_tail.is_file_at(&file_path).await
+                        && let Some(mut old_tail) = tail.take()
+                    {
+                        // Created again without renaming or removing the old one.
+                        if !old_tail.send_lines(&tx, true).await {
+                            break;
+                        }
+                        rotation = Some(FGLogRotation::Recreated);
+                    }
+
+                    if tail.is_none() {
+                        match FGLogTail::open(&file_path).await {
+                            Ok((new_tail, anchor)) => {
+                                let started = FGLogInput::FileStarted {
+                                    anchor,
+                                    rotation: rotation.take(),
+                                };
+                                if tx.send(started).await.is_err() {
+                                    break;
+                                }
+                                tail = Some(new_tail);
+                            }
+                            Err(err) => {
+                                warn!("Could not open the log file {}: {}", file_path, err);
+                                continue;
+                            }
+                        }
+                    }
+                    let Some(current_tail) = &mut tail else {
+                        continue;
+                    };
 
-            if let WatchMessage::ContentModified { length } = watch_msg {
-                // file length is less than buffer size
-                if length < buffer {
-                    unreachable!(
-                        "The log file is less than the buffer size. Maybe the file content has been changed? It should be not happened."
-                    );
+                    if current_tail.is_truncated().await.unwrap_or(false) {
+                        info!("The log file has been truncated, reading from the start");
+                        current_tail.offset = 0;
+                        // The creation time is of the old content, so use the current time.
+                        let started = FGLogInput::FileStarted {
+                            anchor: Temporal::now().instant().ok().map(FGLogAnchor::Started),
+                            rotation: Some(FGLogRotation::Truncated),
+                        };
+                        if tx.send(started).await.is_err() {
+                            break;
+                        }
+                    }
+                    if !current_tail.send_lines(&tx, false).await {
+                        break;
+                    }
                 }
-                let mut file = OpenOptions::new()
-                    .read(true)
-                    .open(&file_path)
-                    .await
-                    .unwrap();
-                file.seek(SeekFrom::Start(buffer)).await.unwrap();
-                let mut lines = BufReader::new(file).lines();
-
-                while let Some(line) = lines.next_line().await.unwrap() {
-                    tx.send(line).await.unwrap();
+                WatchMessage::Renamed | WatchMessage::Removed => {
+                    // Late, the new file has already been opened for it, e.g. the poll has seen
+                    // the rotation before the native event.
+                    if let Some(current_tail) = &tail
+                        && current_tail.is_file_at(&file_path).await
+                    {
+                        continue;
+                    }
+                    let Some(mut old_tail) = tail.take() else {
+                        continue;
+                    };
+                    info!("The log file has been rotated, reading the rest of it");
+                    if !old_tail.send_lines(&tx, true).await {
+                        break;
+                    }
+                    rotation = Some(if watch_msg == WatchMessage::Renamed {
+                        FGLogRotation::Renamed
+                    } else {
+                        FGLogRotation::Recreated
+                    });
                 }
-
-                buffer = length;
+                WatchMessage::Closed => {}
             }
         }
     });
 
     rx
 }
+
+#[test]
+fn split_complete_lines() {
+    let (lines, consumed) =
+        split_lines(b"10:00:00.000: first\r\n10:00:01.000: na\xffme\n10:00:02.000: par");
+    assert_eq!(
+        lines,
+        vec!["10:00:00.000: first", "10:00:01.000: na\u{FFFD}me"]
+    );
+    // The partial line is left to be read again.
+    assert_eq!(consumed, 41);
+}
+
+#[test]
+fn poll_rotation_messages() {
+    let stat = |file_id, len| {
+        Some(FGFileStat {
+            file_id,
+            len,
+            modified: None,
+        })
+    };
+
+    assert_eq!(
+        poll_messages(None, stat(1, 0), false),
+        vec![WatchMessage::FileCreated]
+    );
+    assert_eq!(
+        poll_messages(stat(1, 0), stat(1, 10), false),
+        vec![WatchMessage::ContentModified]
+    );
+    assert!(poll_messages(stat(1, 10), stat(1, 10), false).is_empty());
+    // Player.log has been renamed to Player-prev.log, then created again.
+    assert_eq!(
+        poll_messages(stat(1, 10), stat(2, 0), true),
+        vec![WatchMessage::Renamed, WatchMessage::FileCreated]
+    );
+    assert_eq!(
+        poll_messages(stat(2, 0), None, false),
+        vec![WatchMessage::Removed]
+    );
+}
+
+/// Feeds the watch messages to `read_log_file` for the file in a fresh temporary directory.
+#[cfg(test)]
+struct FGTailTest {
+    dir: crate::test_support::FGTempDir,
+    watch_tx: Sender<WatchMessage>,
+    rx: Receiver<FGLogInput>,
+}
+
+#[cfg(test)]
+impl FGTailTest {
+    async fn new(name: &str, content: &str) -> Self {
+        let dir = crate::test_support::FGTempDir::new(&format!("tail-{}", name));
+        let file_path = dir.path().join("Player.log");
+        std::fs::write(&file_path, content).unwrap();
+
+        let (watch_tx, watch_rx) = mpsc::channel(16);
+        let rx = read_log_file(watch_rx, file_path.to_str().unwrap()).await;
+        Self { dir, watch_tx, rx }
+    }
+
+    fn append(&self, content: &str) {
+        use std::io::Write;
+
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(self.dir.path().join("Player.log"))
+            .unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+    }
+
+    /// Sends the message, then returns the inputs read for it without their start times.
+    async fn watch(&mut self, msg: WatchMessage) -> Vec<FGLogInput> {
+        self.watch_tx.send(msg).await.unwrap();
+        let mut inputs = Vec::new();
+        // The reader sends nothing for some messages, so wait a bit for the last input.
+        while let Ok(Some(input)) =
+            tokio::time::timeout(Duration::from_millis(200), self.rx.recv()).await
+        {
+            inputs.push(match input {
+                FGLogInput::FileStarted { rotation, .. } => FGLogInput::FileStarted {
+                    anchor: None,
+                    rotation,
+                },
+                line => line,
+            });
+        }
+        inputs
+    }
+}
+
+#[cfg(test)]
+fn started(rotation: Option<FGLogRotation>) -> FGLogInput {
+    FGLogInput::FileStarted {
+        anchor: None,
+        rotation,
+    }
+}
+
+#[cfg(test)]
+fn line(line: &str) -> FGLogInput {
+    FGLogInput::Line(line.to_owned())
+}
+
+#[tokio::test]
+async fn read_truncated_log_from_start() {
+    let mut test = FGTailTest::new("truncated", "first\nsecond\n").await;
+    assert_eq!(
+        test.watch(WatchMessage::ContentModified).await,
+        vec![started(None), line("first"), line("second")]
+    );
+
+    std::fs::write(test.dir.path().join("Player.log"), "new\n").unwrap();
+    assert_eq!(
+        test.watch(WatchMessage::ContentModified).await,
+        vec![started(Some(FGLogRotation::Truncated)), line("new")]
+    );
+}
+
+#[tokio::test]
+async fn drain_renamed_log_before_next() {
+    let mut test = FGTailTest::new("renamed", "first\n").await;
+    assert_eq!(
+        test.watch(WatchMessage::ContentModified).await,
+        vec![started(None), line("first")]
+    );
+
+    // The game exits in the middle of the last line, then starts with a new log.
+    test.append("last");
+    std::fs::rename(
+        test.dir.path().join("Player.log"),
+        test.dir.path().join("Player-prev.log"),
+    )
+    .unwrap();
+    std::fs::write(test.dir.path().join("Player.log"), "next\n").unwrap();
+    test.watch_tx.send(WatchMessage::Renamed).await.unwrap();
+    assert_eq!(
+        test.watch(WatchMessage::FileCreated).await,
+        vec![
+            line("last"),
+            started(Some(FGLogRotation::Renamed)),
+            line("next")
+        ]
+    );
+}
+
+#[tokio::test]
+async fn ignore_rotation_after_reopening() {
+    let mut test = FGTailTest::new("late-rename", "first\n").await;
+    assert_eq!(
+        test.watch(WatchMessage::ContentModified).await,
+        vec![started(None), line("first")]
+    );
+
+    std::fs::rename(
+        test.dir.path().join("Player.log"),
+        test.dir.path().join("Player-prev.log"),
+    )
+    .unwrap();
+    std::fs::write(test.dir.path().join("Player.log"), "next\n").unwrap();
+    assert_eq!(
+        test.watch(WatchMessage::FileCreated).await,
+        vec![started(Some(FGLogRotation::Recreated)), line("next")]
+    );
+    // The native event of the rename arrives after the poll has reopened the file.
+    assert!(test.watch(WatchMessage::Renamed).await.is_empty());
+    test.append("after\n");
+    assert_eq!(
+        test.watch(WatchMessage::ContentModified).await,
+        vec![line("after")]
+    );
+}
+
+#[tokio::test]
+async fn buffer_partial_line() {
+    let mut test = FGTailTest::new("partial", "first\npar").await;
+    assert_eq!(
+        test.watch(WatchMessage::ContentModified).await,
+        vec![started(None), line("first")]
+    );
+
+    test.append("tial\r\n");
+    assert_eq!(
+        test.watch(WatchMessage::ContentModified).await,
+        vec![line("partial")]
+    );
+}
@@ -0,0 +1,188 @@
+use axum::{
+    Json, Router,
+    extract::{Query, State},
+    http::{HeaderValue, Method},
+    routing::get,
+};
+use serde::Deserialize;
+use tower_http::cors::{AllowOrigin, CorsLayer};
+
+use crate::config::FGHttpOutput;
+use crate::models::{
+    exports::{FGExportsCurrent, FGExportsLobbyInfo, FGExportsQualification, FGExportsRoster},
+    schema::{FGTimedMessage, FGVersioned},
+};
+
+use super::{FGServerState, ws};
+
+/// The number of events returned by `/events` when `limit` is not given.
+const DEFAULT_EVENTS_LIMIT: usize = 20;
+
+/// Builds the JSON API and the WebSocket feed. Every document is `FGVersioned`.
+///
+/// - `GET /current`: the current `FGExportsCurrent` snapshot.
+/// - `GET /events?limit=N`: the last N parsed messages, oldest first.
+/// - `GET /lobby`: the platform breakdown and latency of the current lobby.
+/// - `GET /qualification`: the players qualified and the slots left in the current round.
+/// - `GET /roster`: the players of the current round.
+/// - `GET /ws`: WebSocket pushing the snapshot on connect, then every message and snapshot.
+///   Only served if `websocket` is set.
+pub fn router(state: FGServerState, options: &FGHttpOutput) -> Router {
+    let mut router = Router::new()
+        .route("/current", get(current))
+        .route("/events", get(events))
+        .route("/lobby", get(lobby))
+        .route("/qualification", get(qualification))
+        .route("/roster", get(roster));
+    if options.websocket {
+        router = router.route("/ws", get(ws::upgrade));
+    }
+    router
+        .layer(cors_layer(&options.cors_origins))
+        .with_state(state)
+}
+
+/// Allows `fetch` from the browser sources (e.g. OBS) which are served from another origin.
+/// The API is read-only, so only `GET` is allowed.
+fn cors_layer(origins: &[String]) -> CorsLayer {
+    let allow_origin = if origins.iter().any(|origin| origin == "*") {
+        AllowOrigin::any()
+    } else {
+        // Validated on load.
+        AllowOrigin::list(
+            origins
+                .iter()
+                .filter_map(|origin| HeaderValue::from_str(origin).ok()),
+        )
+    };
+    CorsLayer::new()
+        .allow_methods([Method::GET])
+        .allow_origin(allow_origin)
+}
+
+async fn current(State(state): State<FGServerState>) -> Json<FGVersioned<FGExportsCurrent>> {
+    Json(FGVersioned::new(state.current_rx.borrow().clone()))
+}
+
+#[derive(Debug, Deserialize)]
+struct EventsQuery {
+    limit: Option<usize>,
+}
+
+async fn events(
+    State(state): State<FGServerState>,
+    Query(query): Query<EventsQuery>,
+) -> Json<Vec<FGVersioned<FGTimedMessage>>> {
+    let limit = query.limit.unwrap_or(DEFAULT_EVENTS_LIMIT);
+    let recent_events = state.recent_events.lock().unwrap();
+    let skip = recent_events.len().saturating_sub(limit);
+    Json(
+        recent_events
+            .iter()
+            .skip(skip)
+            .cloned()
+            .map(FGVersioned::new)
+            .collect(),
+    )
+}
+
+async fn lobby(State(state): State<FGServerState>) -> Json<FGVersioned<FGExportsLobbyInfo>> {
+    let lobby_info = state.current_rx.borrow().lobby_info.clone();
+    Json(FGVersioned::new(lobby_info.unwrap_or_default()))
+}
+
+async fn qualification(
+    State(state): State<FGServerState>,
+) -> Json<FGVersioned<FGExportsQualification>> {
+    let qualification = state.current_rx.borrow().qualification.clone();
+    Json(FGVersioned::new(qualification.unwrap_or_default()))
+}
+
+async fn roster(State(state): State<FGServerState>) -> Json<FGVersioned<FGExportsRoster>> {
+    let roster = state.current_rx.borrow().roster.clone();
+    Json(FGVersioned::new(roster.unwrap_or_default()))
+}
+
+#[cfg(test)]
+async fn get_json(router: Router, uri: &str) -> serde_json::Value {
+    use axum::body::{Body, to_bytes};
+    use tower::ServiceExt;
+
+    let request = axum::http::Request::get(uri).body(Body::empty()).unwrap();
+    let response = router.oneshot(request).await.unwrap();
+    assert!(response.status().is_success(), "{}", uri);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+#[tokio::test]
+async fn serve_seeded_routes() {
+    use std::{
+        collections::{HashMap, VecDeque},
+        sync::{Arc, Mutex},
+    };
+
+    use axum::body::Body;
+    use tokio::sync::{broadcast, watch};
+    use tower::ServiceExt;
+
+    use crate::models::{common::FGPlatform, messages::FGGameMessage, state::FGGameState};
+
+    let current = FGExportsCurrent {
+        state: Some(FGGameState::StateMainMenu),
+        lobby_info: Some(FGExportsLobbyInfo {
+            platforms: HashMap::from([(FGPlatform::Switch, 3)]),
+            latency: Some(42),
+            latency_from_time: None,
+        }),
+        ..Default::default()
+    };
+    let (_current_tx, current_rx) = watch::channel(current);
+    let recent_events = (0..3)
+        .map(|time| FGTimedMessage {
+            time: Some(time),
+            message: FGGameMessage::RequestLocalPlayer(time as _),
+        })
+        .collect::<VecDeque<_>>();
+    let state = FGServerState {
+        current_rx,
+        recent_events: Arc::new(Mutex::new(recent_events)),
+        push_tx: broadcast::channel(16).0,
+    };
+    let router = router(state, &FGHttpOutput::default());
+
+    let current = get_json(router.clone(), "/current").await;
+    assert_eq!(current["state"], "StateMainMenu");
+    let times = |events: serde_json::Value| -> Vec<i64> {
+        events
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|event| event["time"].as_i64().unwrap())
+            .collect()
+    };
+    assert_eq!(times(get_json(router.clone(), "/events").await), [0, 1, 2]);
+    // The newest ones, oldest first.
+    assert_eq!(
+        times(get_json(router.clone(), "/events?limit=2").await),
+        [1, 2]
+    );
+    assert_eq!(
+        times(get_json(router.clone(), "/events?limit=10").await),
+        [0, 1, 2]
+    );
+    assert!(times(get_json(router.clone(), "/events?limit=0").await).is_empty());
+    let lobby = get_json(router.clone(), "/lobby").await;
+    assert_eq!(lobby["platforms"]["switch"], 3);
+    assert_eq!(lobby["latency"], 42);
+
+    // Only reading is allowed from the other origins.
+    let preflight = axum::http::Request::options("/current")
+        .header("origin", "http://localhost:3000")
+        .header("access-control-request-method", "POST")
+        .body(Body::empty())
+        .unwrap();
+    let response = router.oneshot(preflight).await.unwrap();
+    assert_eq!(response.headers()["access-control-allow-origin"], "*");
+    assert_eq!(response.headers()["access-control-allow-methods"], "GET");
+}
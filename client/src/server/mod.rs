@@ -0,0 +1,35 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+use serde::Serialize;
+use tokio::sync::{broadcast, watch};
+
+use crate::models::{
+    exports::FGExportsCurrent,
+    schema::{FGLifecycleEvent, FGTimedMessage, FGVersioned},
+};
+
+pub mod http;
+pub mod task_server;
+pub mod ws;
+
+/// Pushed to the WebSocket clients, tagged by `type`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FGServerPush {
+    Event(FGVersioned<FGTimedMessage>),
+    Lifecycle(FGVersioned<FGLifecycleEvent>),
+    /// Sent on connect, whenever the snapshot changes and after skipping the messages of a slow client.
+    Current(Box<FGVersioned<FGExportsCurrent>>),
+}
+
+/// Shared between the route handlers.
+#[derive(Debug, Clone)]
+pub struct FGServerState {
+    pub current_rx: watch::Receiver<FGExportsCurrent>,
+    /// Latest events, the newest one is at the back.
+    pub recent_events: Arc<Mutex<VecDeque<FGTimedMessage>>>,
+    pub push_tx: broadcast::Sender<FGServerPush>,
+}
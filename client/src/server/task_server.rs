@@ -0,0 +1,104 @@
+use std::{
+    collections::VecDeque,
+    io,
+    sync::{Arc, Mutex},
+};
+
+use temporal_rs::Instant;
+use tokio::{
+    net::TcpListener,
+    sync::{
+        broadcast,
+        mpsc::{self, Receiver},
+        watch,
+    },
+};
+use tracing::{error, info};
+
+use crate::{
+    config::FGHttpOutput,
+    models::{
+        exports::FGExportsCurrent,
+        lifecycle::FGEpisodeEvent,
+        messages::FGGameMessage,
+        schema::{FGLifecycleEvent, FGTimedMessage, FGVersioned},
+    },
+};
+
+use super::{FGServerPush, FGServerState, http::router};
+
+/// Serves the HTTP API, keeping the latest `recent_events` messages for `/events`
+/// and pushing them to the WebSocket clients along with the lifecycle events.
+///
+/// The messages are passed through as-is for the next consumer.
+/// Fails if the address cannot be bound, e.g. it is already in use.
+pub async fn serve_from_rx(
+    mut message_rx: Receiver<(FGGameMessage, Option<Instant>)>,
+    mut current_rx: watch::Receiver<FGExportsCurrent>,
+    mut lifecycle_rx: Receiver<FGEpisodeEvent>,
+    options: &FGHttpOutput,
+) -> io::Result<Receiver<(FGGameMessage, Option<Instant>)>> {
+    let (tx, rx) = mpsc::channel(1024);
+    let addr = options.address;
+    let recent_capacity = options.recent_events;
+    let recent_events = Arc::new(Mutex::new(VecDeque::with_capacity(recent_capacity)));
+
+    // Every WebSocket client subscribes to it, so each one gets the full stream.
+    let (push_tx, _) = broadcast::channel(1024);
+
+    let state = FGServerState {
+        current_rx: current_rx.clone(),
+        recent_events: recent_events.clone(),
+        push_tx: push_tx.clone(),
+    };
+    let router = router(state, options);
+    let listener = TcpListener::bind(addr).await?;
+    info!("Serving HTTP API on http://{}", addr);
+    tokio::spawn(async move {
+        if let Err(err) = axum::serve(listener, router).await {
+            error!("HTTP server has been stopped: {}", err);
+        }
+    });
+
+    let current_push_tx = push_tx.clone();
+    tokio::spawn(async move {
+        while current_rx.changed().await.is_ok() {
+            let current = current_rx.borrow_and_update().clone();
+            // No receivers is not an error, just nobody has connected yet.
+            let _ =
+                current_push_tx.send(FGServerPush::Current(Box::new(FGVersioned::new(current))));
+        }
+    });
+
+    let lifecycle_push_tx = push_tx.clone();
+    tokio::spawn(async move {
+        while let Some(event) = lifecycle_rx.recv().await {
+            let _ = lifecycle_push_tx.send(FGServerPush::Lifecycle(FGVersioned::new(
+                FGLifecycleEvent { event },
+            )));
+        }
+    });
+
+    tokio::spawn(async move {
+        while let Some((message, time)) = message_rx.recv().await {
+            let event = FGTimedMessage {
+                time: time.as_ref().map(|time| time.epoch_milliseconds()),
+                message: message.clone(),
+            };
+            {
+                let mut recent_events = recent_events.lock().unwrap();
+                if recent_events.len() >= recent_capacity {
+                    recent_events.pop_front();
+                }
+                recent_events.push_back(event.clone());
+            }
+            let _ = push_tx.send(FGServerPush::Event(FGVersioned::new(event)));
+
+            if tx.send((message, time)).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    Ok(rx)
+}
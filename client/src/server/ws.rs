@@ -0,0 +1,127 @@
+use axum::{
+    extract::{
+        State, WebSocketUpgrade,
+        ws::{Message, WebSocket},
+    },
+    response::Response,
+};
+use tokio::sync::broadcast::error::RecvError;
+use tracing::warn;
+
+use crate::models::schema::FGVersioned;
+
+use super::{FGServerPush, FGServerState};
+
+/// Upgrades to the WebSocket which pushes every `FGServerPush`.
+pub async fn upgrade(ws: WebSocketUpgrade, State(state): State<FGServerState>) -> Response {
+    ws.on_upgrade(move |socket| push_to_socket(socket, state))
+}
+
+async fn push_to_socket(mut socket: WebSocket, state: FGServerState) {
+    // Subscribe before sending the snapshot, so nothing between them gets lost.
+    let mut push_rx = state.push_tx.subscribe();
+    if send(&mut socket, &current_push(&state)).await.is_err() {
+        return;
+    }
+
+    loop {
+        tokio::select! {
+            push = push_rx.recv() => match push {
+                Ok(push) => {
+                    if send(&mut socket, &push).await.is_err() {
+                        break;
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!("WebSocket client is too slow, skipped {} messages", skipped);
+                    // The skipped messages may have changed the snapshot, so send it again.
+                    if send(&mut socket, &current_push(&state)).await.is_err() {
+                        break;
+                    }
+                }
+                Err(RecvError::Closed) => break,
+            },
+            incoming = socket.recv() => match incoming {
+                // Clients are not expected to send anything, just wait for closing.
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
+
+fn current_push(state: &FGServerState) -> FGServerPush {
+    FGServerPush::Current(Box::new(FGVersioned::new(
+        state.current_rx.borrow().clone(),
+    )))
+}
+
+async fn send(socket: &mut WebSocket, push: &FGServerPush) -> Result<(), axum::Error> {
+    let json = serde_json::to_string(push).map_err(axum::Error::new)?;
+    socket.send(Message::Text(json.into())).await
+}
+
+#[tokio::test]
+async fn push_snapshot_then_events() {
+    use std::{
+        collections::VecDeque,
+        sync::{Arc, Mutex},
+    };
+
+    use futures_util::StreamExt;
+    use tokio::{
+        net::TcpListener,
+        sync::{broadcast, watch},
+    };
+
+    use crate::{
+        config::FGHttpOutput,
+        models::{
+            exports::FGExportsCurrent, messages::FGGameMessage, schema::FGTimedMessage,
+            state::FGGameState,
+        },
+    };
+
+    let current = FGExportsCurrent {
+        state: Some(FGGameState::StateMainMenu),
+        ..Default::default()
+    };
+    let (_current_tx, current_rx) = watch::channel(current);
+    let (push_tx, _) = broadcast::channel(2);
+    let state = FGServerState {
+        current_rx,
+        recent_events: Arc::new(Mutex::new(VecDeque::new())),
+        push_tx: push_tx.clone(),
+    };
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let router = super::http::router(state, &FGHttpOutput::default());
+    tokio::spawn(async move { axum::serve(listener, router).await });
+
+    let (mut socket, _) = tokio_tungstenite::connect_async(format!("ws://{}/ws", addr))
+        .await
+        .unwrap();
+    let mut next_type = async || {
+        let message = socket.next().await.unwrap().unwrap();
+        let push: serde_json::Value = serde_json::from_str(message.to_text().unwrap()).unwrap();
+        push["type"].as_str().unwrap().to_owned()
+    };
+    assert_eq!(next_type().await, "current");
+
+    let event = |time| {
+        FGServerPush::Event(FGVersioned::new(FGTimedMessage {
+            time: Some(time),
+            message: FGGameMessage::RequestLocalPlayer(21),
+        }))
+    };
+    push_tx.send(event(0)).unwrap();
+    assert_eq!(next_type().await, "event");
+
+    // Sent at once without yielding, so the socket falls behind the capacity of 2.
+    for time in 1..=4 {
+        push_tx.send(event(time)).unwrap();
+    }
+    assert_eq!(next_type().await, "current");
+    assert_eq!(next_type().await, "event");
+    assert_eq!(next_type().await, "event");
+}
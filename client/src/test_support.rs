@@ -0,0 +1,49 @@
+//! Fixtures shared by the unit tests and the fuzz targets.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use temporal_rs::{PlainDate, TimeZone};
+
+use crate::parser::log_clock::FGLogClock;
+
+/// The clock the test logs are read with, on 2025-06-15 in +09:00.
+pub fn test_clock() -> FGLogClock {
+    FGLogClock::new(
+        TimeZone::try_from_str("+09:00").unwrap(),
+        PlainDate::try_new_iso(2025, 6, 15).unwrap(),
+    )
+}
+
+/// An empty temporary directory of its own, removed when dropped, even if the test panics.
+pub struct FGTempDir(PathBuf);
+
+impl FGTempDir {
+    /// The tests in the same binary run in parallel, so the process ID is not enough.
+    pub fn new(name: &str) -> Self {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        let dir = std::env::temp_dir().join(format!(
+            "fg-{}-{}-{}",
+            name,
+            std::process::id(),
+            NEXT.fetch_add(1, Ordering::Relaxed)
+        ));
+        // Left over by a killed process of the same ID.
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        Self(dir)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for FGTempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
@@ -0,0 +1,67 @@
+use std::time::Duration;
+
+use tokio::{
+    sync::mpsc::{self, Receiver},
+    task::JoinHandle,
+};
+use tracing::{error, warn};
+
+use crate::{
+    config::FGWebhookOutput,
+    models::{
+        lifecycle::FGEpisodeEvent,
+        schema::{FGLifecycleEvent, FGVersioned},
+    },
+};
+
+/// Gives up on the webhook after this, so a slow one does not hold back the next events.
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// POSTs every lifecycle event to the webhooks as `FGVersioned<FGLifecycleEvent>`, in order.
+///
+/// The events are passed through as-is for the next consumer, before being posted.
+/// The handle finishes once every event has been posted. The webhooks are disabled if the HTTP
+/// client cannot be built, e.g. the TLS initialisation has failed.
+pub async fn post_from_rx(
+    mut event_rx: Receiver<FGEpisodeEvent>,
+    webhooks: Vec<FGWebhookOutput>,
+) -> (Receiver<FGEpisodeEvent>, JoinHandle<()>) {
+    let (tx, rx) = mpsc::channel(1024);
+    let client = match reqwest::Client::builder().timeout(WEBHOOK_TIMEOUT).build() {
+        Ok(client) => Some(client),
+        Err(err) => {
+            error!(
+                "Could not build the webhook client, not posting the events: {}",
+                err
+            );
+            None
+        }
+    };
+
+    let handle = tokio::spawn(async move {
+        while let Some(event) = event_rx.recv().await {
+            let document = FGVersioned::new(FGLifecycleEvent {
+                event: event.clone(),
+            });
+            // Nobody listening to the events should not stop the webhooks.
+            let _ = tx.send(event).await;
+
+            let Some(client) = &client else {
+                continue;
+            };
+            for webhook in &webhooks {
+                let result = client
+                    .post(&webhook.url)
+                    .json(&document)
+                    .send()
+                    .await
+                    .and_then(|response| response.error_for_status());
+                if let Err(err) = result {
+                    warn!("Could not post the event to {}: {}", webhook.url, err);
+                }
+            }
+        }
+    });
+
+    (rx, handle)
+}
@@ -0,0 +1,161 @@
+//! Parses the log excerpts in `tests/corpus` and compares the messages with their snapshots.
+//!
+//! Each `<scenario>.log` has a `<scenario>.snap` next to it, one JSON line per message or error.
+//! Run with `FG_UPDATE_SNAPSHOTS=1` to rewrite the snapshots after an intended change.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    sync::Once,
+};
+
+use fg_telemetry_client::{
+    extra_data::{DEFAULT_LOCALE, load_extra_data_assets},
+    parser::{log_parser::FGLogParser, rules::rules},
+};
+use serde_json::json;
+
+use common::test_clock;
+
+mod common;
+
+fn corpus_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/corpus")
+}
+
+/// The excerpts, sorted by name.
+fn corpus_logs() -> Vec<PathBuf> {
+    let mut logs: Vec<_> = fs::read_dir(corpus_dir())
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "log"))
+        .collect();
+    logs.sort();
+    logs
+}
+
+/// The rounds and their rules come from the fixture assets, so the snapshots don't depend on the
+/// embedded ones.
+fn load_corpus_assets() {
+    static LOADED: Once = Once::new();
+    LOADED.call_once(|| {
+        load_extra_data_assets(Some(&corpus_dir().join("extra_datas")), DEFAULT_LOCALE).unwrap();
+    });
+}
+
+fn parse_log(log: &str) -> String {
+    let mut parser = FGLogParser::new(test_clock());
+
+    let mut snapshot = String::new();
+    for line in log.lines() {
+        let entry = match parser.parse_line(line) {
+            None => continue,
+            Some(Ok((message, time))) => json!({
+                "time": time.map(|time| time.epoch_milliseconds()),
+                "message": message,
+            }),
+            Some(Err(err)) => json!({
+                "error": { "rule": err.rule, "cause": err.cause.to_string() },
+            }),
+        };
+        snapshot.push_str(&entry.to_string());
+        snapshot.push('\n');
+    }
+    snapshot
+}
+
+#[test]
+fn corpus_matches_snapshots() {
+    load_corpus_assets();
+    let update = env::var_os("FG_UPDATE_SNAPSHOTS").is_some();
+
+    let mut mismatched = Vec::new();
+    for log_path in corpus_logs() {
+        let actual = parse_log(&fs::read_to_string(&log_path).unwrap());
+        let snap_path = log_path.with_extension("snap");
+        if update {
+            fs::write(&snap_path, &actual).unwrap();
+            continue;
+        }
+
+        let expected = fs::read_to_string(&snap_path).unwrap_or_default();
+        if expected != actual {
+            // Report the first differing line, the whole snapshot is too long to read.
+            let (line, expected, actual) = expected
+                .lines()
+                .map(Some)
+                .chain(std::iter::repeat(None))
+                .zip(actual.lines().map(Some).chain(std::iter::repeat(None)))
+                .enumerate()
+                .find(|(_, (expected, actual))| expected != actual)
+                .map(|(line, (expected, actual))| (line + 1, expected, actual))
+                .unwrap();
+            mismatched.push(format!(
+                "{} line {}:\n  expected: {}\n  actual:   {}",
+                snap_path.display(),
+                line,
+                expected.unwrap_or("<end>"),
+                actual.unwrap_or("<end>")
+            ));
+        }
+    }
+    assert!(
+        mismatched.is_empty(),
+        "the snapshots differ, run with FG_UPDATE_SNAPSHOTS=1 if intended\n{}",
+        mismatched.join("\n")
+    );
+}
+
+#[test]
+fn corpus_covers_every_rule() {
+    let snapshots: String = corpus_logs()
+        .iter()
+        .map(|log_path| fs::read_to_string(log_path.with_extension("snap")).unwrap_or_default())
+        .collect();
+
+    // The messages are named after the rules producing them.
+    let uncovered: Vec<_> = rules()
+        .iter()
+        .map(|rule| rule.name)
+        .filter(|name| !snapshots.contains(&format!("\"type\":\"{}\"", name)))
+        .collect();
+    assert!(
+        uncovered.is_empty(),
+        "no corpus excerpt for the rules {:?}",
+        uncovered
+    );
+}
+
+/// Replays every excerpt at once, as `replay Player-prev.log Player.log` does.
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn replay_corpus_in_order() {
+    use fg_telemetry_client::parser::{FGLogInput, task_replay::replay_log_files};
+
+    let mut files = corpus_logs();
+    // Missing files are skipped without a start.
+    files.insert(1, corpus_dir().join("missing.log"));
+    let mut input_rx = replay_log_files(files).await;
+    let mut inputs = Vec::new();
+    while let Some(input) = input_rx.recv().await {
+        inputs.push(match input {
+            FGLogInput::FileStarted { anchor, rotation } => {
+                assert!(anchor.is_some());
+                // Replayed files are not reported as rotated.
+                assert_eq!(rotation, None);
+                "<started>".to_owned()
+            }
+            FGLogInput::Line(line) => line,
+        });
+    }
+
+    let expected: Vec<_> = corpus_logs()
+        .iter()
+        .flat_map(|log_path| {
+            let log = fs::read_to_string(log_path).unwrap();
+            std::iter::once("<started>".to_owned())
+                .chain(log.lines().map(str::to_owned).collect::<Vec<_>>())
+        })
+        .collect();
+    assert_eq!(inputs, expected);
+}
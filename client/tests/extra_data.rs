@@ -0,0 +1,48 @@
+//! Loads the assets from a data directory, in its own process as they are global.
+
+use std::{fs, str::FromStr};
+
+use fg_telemetry_client::{
+    extra_data::{DEFAULT_LOCALE, load_extra_data_assets},
+    models::common::FGGameMode,
+};
+
+use common::FGTempDir;
+
+mod common;
+
+#[test]
+fn reload_resolves_new_shows() {
+    let temp_dir = FGTempDir::new("extra-data");
+    let dir = temp_dir.path();
+    let show = FGGameMode::from_str("corpus_new_show").unwrap();
+    assert_eq!(show, FGGameMode::Unknown("corpus_new_show".to_owned()));
+
+    fs::write(
+        dir.join("shows.json"),
+        r#"[{
+            "id": "corpus_new_show",
+            "show_name": null,
+            "show_description": null,
+            "content_label": "",
+            "min_party_size": 1,
+            "max_party_size": 4,
+            "show_type": { "showtype_switch": "individual" },
+            "episode_reward_settings_id": ""
+        }]"#,
+    )
+    .unwrap();
+    load_extra_data_assets(Some(dir), DEFAULT_LOCALE).unwrap();
+    assert!(matches!(
+        FGGameMode::from_str("corpus_new_show").unwrap(),
+        FGGameMode::Extra { id, .. } if id == "corpus_new_show"
+    ));
+
+    // Invalid files keep the previous assets.
+    fs::write(dir.join("shows.json"), "[").unwrap();
+    assert!(load_extra_data_assets(Some(dir), DEFAULT_LOCALE).is_err());
+    assert!(matches!(
+        FGGameMode::from_str("corpus_new_show").unwrap(),
+        FGGameMode::Extra { .. }
+    ));
+}
@@ -0,0 +1,69 @@
+//! Reloads the assets when the watched directory changes, in its own process as they are global.
+#![cfg(feature = "watch")]
+
+use std::{fs, str::FromStr, time::Duration};
+
+use fg_telemetry_client::{
+    extra_data::{DEFAULT_LOCALE, task_reload::reload_on_change},
+    models::common::FGGameMode,
+    parser::task_watch::FGWatchMode,
+};
+use tokio::time::{sleep, timeout};
+
+use common::FGTempDir;
+
+mod common;
+
+fn show_json(id: &str) -> String {
+    format!(
+        r#"[{{
+            "id": "{}",
+            "show_name": null,
+            "show_description": null,
+            "content_label": "",
+            "min_party_size": 1,
+            "max_party_size": 4,
+            "show_type": {{ "showtype_switch": "individual" }},
+            "episode_reward_settings_id": ""
+        }}]"#,
+        id
+    )
+}
+
+fn is_known(id: &str) -> bool {
+    matches!(FGGameMode::from_str(id).unwrap(), FGGameMode::Extra { .. })
+}
+
+/// Polls until the assets are as expected, they are reloaded in the background.
+async fn wait_until(condition: impl Fn() -> bool) {
+    timeout(Duration::from_secs(10), async {
+        while !condition() {
+            sleep(Duration::from_millis(20)).await;
+        }
+    })
+    .await
+    .expect("the assets have not been reloaded");
+}
+
+#[tokio::test]
+async fn reload_on_file_change() {
+    let temp_dir = FGTempDir::new("extra-data-reload");
+    // Mounted after the start, e.g. a network share.
+    let dir = temp_dir.path().join("extra_datas");
+    assert!(!is_known("reload_first_show"));
+
+    // Polling, as on the file systems without native events.
+    let handle = reload_on_change(
+        dir.clone(),
+        DEFAULT_LOCALE.to_owned(),
+        FGWatchMode::Poll,
+        Duration::from_millis(50),
+    );
+    fs::create_dir(&dir).unwrap();
+    fs::write(dir.join("shows.json"), show_json("reload_first_show")).unwrap();
+    wait_until(|| is_known("reload_first_show")).await;
+
+    fs::write(dir.join("shows.json"), show_json("reload_second_show")).unwrap();
+    wait_until(|| is_known("reload_second_show") && !is_known("reload_first_show")).await;
+    handle.abort();
+}